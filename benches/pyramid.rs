@@ -7,16 +7,12 @@ use test::Bencher;
 
 use physics2d::*;
 
-fn box_vertices(w: f32, h: f32) -> Vec<Vec2> {
-    vec![Vec2::ZERO, Vec2::RIGHT * w, Vec2::new(w, h), Vec2::UP * h]
-}
-
 fn initialize(pyramid_base_count: u32) -> World {
     let mut world = World::default();
     
     let ground_width = pyramid_base_count as f32 * 1.5;
     let ground_height = 1.0;
-    let ground_poly = shapes::Polygon::new(box_vertices(ground_width, ground_height));
+    let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
     
     let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
     
@@ -24,7 +20,7 @@ fn initialize(pyramid_base_count: u32) -> World {
     world.add_body(ground);
     
     const WIDTH: f32 = 1.0;
-    let square = shapes::Polygon::new(box_vertices(WIDTH, WIDTH));
+    let square = shapes::Polygon::rect(WIDTH, WIDTH);
     
     let mut x = Vec2::new(WIDTH * 0.5, WIDTH * 0.5 + ground_height);
     
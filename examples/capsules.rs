@@ -0,0 +1,98 @@
+extern crate physics2d;
+
+mod testbed;
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+struct CapsulesTestbed {
+    world: World,
+}
+
+impl CapsulesTestbed {
+    pub fn new(config: &testbed::Config) -> CapsulesTestbed {
+        let window_width = config.window_width as f32 / config.pixels_per_unit;
+        let window_height = config.window_height as f32 / config.pixels_per_unit;
+        
+        let mut world = World::default();
+        
+        let ground_width = window_width;
+        let ground_height = 1.0;
+        let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
+        
+        let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
+        ground.transform.position.y = -window_height / 2.0 + ground_height / 2.0;
+        ground.set_static();
+        world.add_body(ground);
+        
+        // Ramps
+        for &(x, y, rotation) in [(-15.0, 8.0, -0.3), (12.0, -5.0, 0.3)].iter() {
+            let ramp = shapes::Capsule::new(12.0, 0.5);
+            
+            let mut body = Body::new(ramp.into_shape(), 10.0, Material::new(0.4, 0.2));
+            body.transform.position = Vec2::new(x, y);
+            body.transform.set_rotation(rotation);
+            body.set_static();
+            world.add_body(body);
+        }
+        
+        // Pile of capsules
+        for i in 0..12 {
+            let capsule = shapes::Capsule::new(1.0 + 0.25 * (i % 3) as f32, 0.6);
+            
+            let mut body = Body::new(capsule.into_shape(), 10.0, Material::new(0.3, 0.2));
+            body.transform.position = Vec2::new(-20.0 + 3.0 * (i % 4) as f32, 15.0 + 2.0 * (i / 4) as f32);
+            body.transform.set_rotation(0.4 * i as f32);
+            world.add_body(body);
+        }
+        
+        CapsulesTestbed {
+            world,
+        }
+    }
+}
+
+impl testbed::Testbed for CapsulesTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.left_mouse_released {
+            let capsule = shapes::Capsule::new(1.5, 0.75);
+            
+            let mut body = Body::new(capsule.into_shape(), 10.0, Material::new(0.3, 0.2));
+            
+            body.transform.position = input.mouse_position;
+            body.transform.set_rotation(0.2);
+            
+            self.world.add_body(body);
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        for body in self.world.bodies_iter() {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", self.world.body_count()), 16);
+        
+        for contact in self.world.contacts() {
+            canvas.draw_point(contact.position);
+            canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+        }
+    }
+}
+
+fn main() {
+    let config = testbed::Config {
+        title: "Capsules".to_string(),
+        window_width: 800,
+        window_height: 600,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = CapsulesTestbed::new(&config);
+    
+    testbed::run(testbed, config);
+}
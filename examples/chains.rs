@@ -0,0 +1,91 @@
+extern crate physics2d;
+
+mod testbed;
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+struct ChainsTestbed {
+    world: World,
+}
+
+impl ChainsTestbed {
+    pub fn new(config: &testbed::Config) -> ChainsTestbed {
+        let window_width = config.window_width as f32 / config.pixels_per_unit;
+        let window_height = config.window_height as f32 / config.pixels_per_unit;
+        
+        let mut world = World::default();
+        
+        // Tiled ground, with a vertex at every tile seam, rising into a slope on the right
+        let mut ground_vertices: Vec<Vec2> = (0..=40)
+            .map(|i| Vec2::new(-window_width / 2.0 + 2.0 * i as f32, -window_height / 2.0 + 2.0))
+            .collect();
+        
+        ground_vertices.push(Vec2::new(window_width / 2.0, -window_height / 2.0 + 12.0));
+        
+        let mut ground = Body::new(shapes::Chain::new(ground_vertices).into_shape(), 0.0, Material::new(0.1, 0.0));
+        ground.set_static();
+        world.add_body(ground);
+        
+        // A closed loop floating in the middle
+        let loop_vertices = vec![
+            Vec2::new(-8.0, 0.0),
+            Vec2::new(8.0, 0.0),
+            Vec2::new(4.0, 4.0),
+            Vec2::new(-4.0, 4.0),
+        ];
+        
+        let mut obstacle = Body::new(shapes::Chain::new_loop(loop_vertices).into_shape(), 0.0, Material::new(0.1, 0.0));
+        obstacle.set_static();
+        world.add_body(obstacle);
+        
+        ChainsTestbed {
+            world,
+        }
+    }
+}
+
+impl testbed::Testbed for ChainsTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.left_mouse_released {
+            let polygon = shapes::Polygon::rect(2.0, 2.0);
+            
+            let mut body = Body::new(polygon.into_shape(), 10.0, Material::new(0.1, 0.0));
+            
+            body.transform.position = input.mouse_position;
+            body.velocity = Vec2::RIGHT * 15.0;
+            
+            self.world.add_body(body);
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        for body in self.world.bodies_iter() {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", self.world.body_count()), 16);
+        
+        for contact in self.world.contacts() {
+            canvas.draw_point(contact.position);
+            canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+        }
+    }
+}
+
+fn main() {
+    let config = testbed::Config {
+        title: "Chains".to_string(),
+        window_width: 800,
+        window_height: 600,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = ChainsTestbed::new(&config);
+    
+    testbed::run(testbed, config);
+}
@@ -39,7 +39,7 @@ impl testbed::Testbed for CircleCollisionsTestbed {
     fn sfml_loop(&mut self, _input: &testbed::Input, dt: f32) {
         if !self.should_stop {
             let body = self.world.get_body_mut(self.body_b);
-            let f = Vec2::UP * 7.0 * body.mass;
+            let f = Vec2::UP * 7.0 * body.mass();
             body.add_force(f);
         }
         
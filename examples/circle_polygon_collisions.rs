@@ -14,12 +14,7 @@ struct CirclePolygonCollisionsTestbed {
 
 impl CirclePolygonCollisionsTestbed {
     pub fn new() -> CirclePolygonCollisionsTestbed {
-        fn vert_box(w: f32, h: f32) -> Vec<Vec2> {
-            vec![Vec2::ZERO, Vec2::RIGHT * w, Vec2::new(w, h), Vec2::UP * h]
-        }
-        
-        let vert_a = vert_box(5.0, 10.0);
-        let poly = shapes::Polygon::new(vert_a);
+        let poly = shapes::Polygon::rect(5.0, 10.0);
         
         let circle = shapes::Circle::new(5.0);
         
@@ -46,7 +41,7 @@ impl testbed::Testbed for CirclePolygonCollisionsTestbed {
     fn sfml_loop(&mut self, _input: &testbed::Input, dt: f32) {
         if !self.should_stop {
             let body = self.world.get_body_mut(self.body_b);
-            let f = Vec2::UP * 7.0 * body.mass;
+            let f = Vec2::UP * 7.0 * body.mass();
             body.add_force(f);
         }
         
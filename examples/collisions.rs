@@ -18,8 +18,7 @@ impl CollisionsTestbed {
         let ground_width = window_width / 2.0;
         let ground_height = window_height / 10.0;
         
-        let ground_vertices = box_vertices(ground_width, ground_height);
-        let ground_poly = shapes::Polygon::new(ground_vertices);
+        let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
         
         let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(1.2, 0.2));
         ground.transform.position.y = -window_height / 2.0 + ground_height / 2.0 + 0.1;
@@ -45,8 +44,7 @@ impl CollisionsTestbed {
 impl testbed::Testbed for CollisionsTestbed {
     fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
         if input.left_mouse_released {
-            let vertices = box_vertices(5.0, 5.0);
-            let polygon = shapes::Polygon::new(vertices);
+            let polygon = shapes::Polygon::rect(5.0, 5.0);
             
             let mut body = Body::new(polygon.into_shape(), 10.0, Material::new(0.3, 0.3));
             
@@ -77,10 +75,6 @@ impl testbed::Testbed for CollisionsTestbed {
     }
 }
 
-fn box_vertices(w: f32, h: f32) -> Vec<Vec2> {
-    vec![Vec2::ZERO, Vec2::RIGHT * w, Vec2::new(w, h), Vec2::UP * h]
-}
-
 fn main() {
     let config = testbed::Config {
         title: "Collisions".to_string(),
@@ -0,0 +1,110 @@
+extern crate physics2d;
+
+mod testbed;
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+struct ConcaveTestbed {
+    world: World,
+    outline_kind: usize,
+}
+
+impl ConcaveTestbed {
+    pub fn new(config: &testbed::Config) -> ConcaveTestbed {
+        let window_width = config.window_width as f32 / config.pixels_per_unit;
+        let window_height = config.window_height as f32 / config.pixels_per_unit;
+        
+        let mut world = World::default();
+        
+        let ground_width = window_width;
+        let ground_height = 1.0;
+        let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
+        
+        let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
+        ground.transform.position.y = -window_height / 2.0 + ground_height / 2.0;
+        ground.set_static();
+        world.add_body(ground);
+        
+        ConcaveTestbed {
+            world,
+            outline_kind: 0,
+        }
+    }
+}
+
+/// A five-pointed star.
+fn star() -> Vec<Vec2> {
+    (0..10)
+        .map(|i| {
+            let angle = math::PI / 2.0 + i as f32 * math::PI / 5.0;
+            let radius = if i % 2 == 0 { 3.0 } else { 1.2 };
+            Vec2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+/// A U shape, with its origin at the bottom left corner.
+fn cup() -> Vec<Vec2> {
+    vec![
+        Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(3.0, 4.0),
+        Vec2::new(3.0, 1.0), Vec2::new(1.0, 1.0), Vec2::new(1.0, 4.0), Vec2::new(0.0, 4.0),
+    ]
+}
+
+/// Builds a body from a concave outline, with a fixture for each convex part.
+fn concave_body(outline: &[Vec2]) -> Body {
+    let fixtures = shapes::decompose(outline).unwrap()
+        .into_iter()
+        .map(|part| Fixture::new(part.into_shape(), 10.0, Material::new(0.3, 0.2)))
+        .collect();
+    
+    Body::with_fixtures(fixtures)
+}
+
+impl testbed::Testbed for ConcaveTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.left_mouse_released {
+            let outline = if self.outline_kind % 2 == 0 { star() } else { cup() };
+            self.outline_kind += 1;
+            
+            let mut body = concave_body(&outline);
+            
+            body.transform.position = input.mouse_position;
+            body.transform.set_rotation(0.3);
+            
+            self.world.add_body(body);
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        for body in self.world.bodies_iter() {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", self.world.body_count()), 16);
+        canvas.draw_text("Click to drop a star or a cup".to_string(), 16);
+        
+        for contact in self.world.contacts() {
+            canvas.draw_point(contact.position);
+            canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+        }
+    }
+}
+
+fn main() {
+    let config = testbed::Config {
+        title: "Concave polygons".to_string(),
+        window_width: 800,
+        window_height: 600,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = ConcaveTestbed::new(&config);
+    
+    testbed::run(testbed, config);
+}
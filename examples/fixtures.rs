@@ -0,0 +1,123 @@
+extern crate physics2d;
+
+mod testbed;
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+/// The collision category of the balls, which pass through each other but not through the rest
+/// of the scene.
+const BALL_CATEGORY: u16 = 0x0002;
+
+struct FixturesTestbed {
+    world: World,
+    body_kind: usize,
+}
+
+impl FixturesTestbed {
+    pub fn new(config: &testbed::Config) -> FixturesTestbed {
+        let window_width = config.window_width as f32 / config.pixels_per_unit;
+        let window_height = config.window_height as f32 / config.pixels_per_unit;
+        
+        let mut world = World::default();
+        
+        let ground_width = window_width;
+        let ground_height = 1.0;
+        let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
+        
+        let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
+        ground.transform.position.y = -window_height / 2.0 + ground_height / 2.0;
+        ground.set_static();
+        world.add_body(ground);
+        
+        FixturesTestbed {
+            world,
+            body_kind: 0,
+        }
+    }
+}
+
+/// A bar with a heavy ball at each end.
+fn dumbbell() -> Body {
+    let bar = shapes::Polygon::rect(4.0, 0.5);
+    
+    let mut fixtures = vec![Fixture::new(bar.into_shape(), 10.0, Material::new(0.3, 0.2))];
+    
+    for &x in [-2.0, 2.0].iter() {
+        let mut ball = Fixture::new(shapes::Circle::new(1.0).into_shape(), 20.0, Material::new(0.3, 0.2));
+        ball.transform = Transform::new(Vec2::new(x, 0.0), 0.0);
+        fixtures.push(ball);
+    }
+    
+    Body::with_fixtures(fixtures)
+}
+
+/// Two crossed planks.
+fn cross() -> Body {
+    let plank = shapes::Polygon::rect(5.0, 1.0).into_shape();
+    
+    let horizontal = Fixture::new(plank.clone(), 10.0, Material::new(0.3, 0.2));
+    
+    let mut vertical = Fixture::new(plank, 10.0, Material::new(0.3, 0.2));
+    vertical.transform = Transform::new(Vec2::ZERO, math::PI / 2.0);
+    
+    Body::with_fixtures(vec![horizontal, vertical])
+}
+
+/// A ball that only collides with bodies that are not balls.
+fn ball() -> Body {
+    let mut ball = Fixture::new(shapes::Circle::new(1.5).into_shape(), 10.0, Material::new(0.3, 0.2));
+    ball.filter = Filter::new(BALL_CATEGORY, !BALL_CATEGORY, 0);
+    
+    Body::with_fixtures(vec![ball])
+}
+
+impl testbed::Testbed for FixturesTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.left_mouse_released {
+            let mut body = match self.body_kind % 3 {
+                0 => dumbbell(),
+                1 => cross(),
+                _ => ball(),
+            };
+            
+            self.body_kind += 1;
+            
+            body.transform.position = input.mouse_position;
+            body.transform.set_rotation(0.3);
+            
+            self.world.add_body(body);
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        for body in self.world.bodies_iter() {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", self.world.body_count()), 16);
+        canvas.draw_text("Click to drop a dumbbell, a cross or a ball".to_string(), 16);
+        
+        for contact in self.world.contacts() {
+            canvas.draw_point(contact.position);
+            canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+        }
+    }
+}
+
+fn main() {
+    let config = testbed::Config {
+        title: "Fixtures".to_string(),
+        window_width: 800,
+        window_height: 600,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = FixturesTestbed::new(&config);
+    
+    testbed::run(testbed, config);
+}
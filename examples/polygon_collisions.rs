@@ -14,15 +14,9 @@ struct PolygonCollisionsTestbed {
 
 impl PolygonCollisionsTestbed {
     pub fn new() -> PolygonCollisionsTestbed {
-        fn vert_box(w: f32, h: f32) -> Vec<Vec2> {
-            vec![Vec2::ZERO, Vec2::RIGHT * w, Vec2::new(w, h), Vec2::UP * h]
-        }
-        
-        let vert_a = vert_box(5.0, 10.0);
-        let poly_a = shapes::Polygon::new(vert_a);
+        let poly_a = shapes::Polygon::rect(5.0, 10.0);
         
-        let vert_b = vert_box(10.0, 5.0);
-        let poly_b = shapes::Polygon::new(vert_b);
+        let poly_b = shapes::Polygon::rect(10.0, 5.0);
         
         let mut a = Body::new(poly_a.into_shape(), 10.0, Material::default());
         let b = Body::new(poly_b.into_shape(), 10.0, Material::default());
@@ -47,7 +41,7 @@ impl testbed::Testbed for PolygonCollisionsTestbed {
     fn sfml_loop(&mut self, _input: &testbed::Input, dt: f32) {
         if !self.should_stop {
             let body = self.world.get_body_mut(self.body_b);
-            let f = Vec2::UP * 7.0 * body.mass;
+            let f = Vec2::UP * 7.0 * body.mass();
             body.add_force(f);
         }
         
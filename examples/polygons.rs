@@ -45,7 +45,7 @@ fn main() {
                                  .into_shape(),
                              1.0, Material::new(0.3, 0.3));
     
-    let torque = math::PI * 5000.0 * body.inertia;
+    let torque = math::PI * 5000.0 * body.inertia();
     body.add_torque(torque);
     
     testbed.world.add_body(body);
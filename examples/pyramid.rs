@@ -20,7 +20,7 @@ impl PyramidTestbed {
         
         let ground_width = window_width;
         let ground_height = 1.0;
-        let ground_poly = shapes::Polygon::new(box_vertices(ground_width, ground_height));
+        let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
         
         let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
         ground.transform.position.y = -window_height / 2.0 + ground_height / 2.0;
@@ -28,14 +28,14 @@ impl PyramidTestbed {
         ground.set_static();
         world.add_body(ground);
         
-        let wall_poly = shapes::Polygon::new(box_vertices(1.0, window_height - ground_height));
+        let wall_poly = shapes::Polygon::rect(1.0, window_height - ground_height);
         
         let mut left_wall = Body::new(wall_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
         left_wall.transform.position = Vec2::new(-window_width / 2.0 + 1.0, 0.6);
         left_wall.set_static();
         world.add_body(left_wall);
         
-        let wall_poly = shapes::Polygon::new(box_vertices(1.0, window_height - ground_height));
+        let wall_poly = shapes::Polygon::rect(1.0, window_height - ground_height);
         
         let mut right_wall = Body::new(wall_poly.into_shape(), 10.0, Material::new(0.4, 0.4));
         right_wall.transform.position = Vec2::new(window_width / 2.0 - 1.0, 0.6);
@@ -43,7 +43,7 @@ impl PyramidTestbed {
         world.add_body(right_wall);
         
         const WIDTH: f32 = 2.5;
-        let square = shapes::Polygon::new(box_vertices(WIDTH, WIDTH));
+        let square = shapes::Polygon::rect(WIDTH, WIDTH);
         
         //  Pyramid (taken directly from Box2D Lite)
         let mut x = Vec2::new(-window_width / 2.0 + WIDTH * 0.5 + 2.0, -window_height / 2.0 + WIDTH * 0.5 + ground_height);
@@ -74,8 +74,7 @@ impl PyramidTestbed {
 impl testbed::Testbed for PyramidTestbed {
     fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
         if input.left_mouse_released {
-            let vertices = box_vertices(5.0, 5.0);
-            let polygon = shapes::Polygon::new(vertices);
+            let polygon = shapes::Polygon::rect(5.0, 5.0);
             
             let mut body = Body::new(polygon.into_shape(), 10.0, Material::new(0.3, 0.3));
             
@@ -113,10 +112,6 @@ impl testbed::Testbed for PyramidTestbed {
     }
 }
 
-fn box_vertices(w: f32, h: f32) -> Vec<Vec2> {
-    vec![Vec2::ZERO, Vec2::RIGHT * w, Vec2::new(w, h), Vec2::UP * h]
-}
-
 fn main() {
     let config = testbed::Config {
         title: "Pyramid".to_string(),
@@ -0,0 +1,76 @@
+extern crate physics2d;
+
+mod testbed;
+
+use std::{env, fs, io};
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+struct SceneTestbed {
+    world: World,
+    debug_draw: bool,
+}
+
+impl SceneTestbed {
+    pub fn new(path: &str) -> SceneTestbed {
+        let file = fs::File::open(path).expect("Could not open scene file");
+        
+        let mut world = World::default();
+        world.load_scene(io::BufReader::new(file)).expect("Could not load scene");
+        
+        SceneTestbed {
+            world,
+            debug_draw: false,
+        }
+    }
+}
+
+impl testbed::Testbed for SceneTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.pressed_keys.contains(&testbed::Key::D) {
+            self.debug_draw = !self.debug_draw;
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        let bodies = self.world.bodies_iter();
+        let body_count = self.world.body_count();
+        
+        for body in bodies {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", body_count), 16);
+        canvas.draw_text(format!("Debug Draw (D to toggle): {}", self.debug_draw), 16);
+        
+        if self.debug_draw {
+            for contact in self.world.contacts() {
+                canvas.draw_point(contact.position);
+                canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+            }
+        }
+    }
+}
+
+/// Opens the scene file given as the first argument, or the pyramid scene if none is given:
+///
+///     cargo run --example=scene --features=scene -- examples/scenes/stacks.json
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| "examples/scenes/pyramid.json".to_string());
+    
+    let config = testbed::Config {
+        title: format!("Scene: {}", path),
+        window_width: 1280,
+        window_height: 720,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = SceneTestbed::new(&path);
+    
+    testbed::run(testbed, config);
+}
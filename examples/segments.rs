@@ -0,0 +1,98 @@
+extern crate physics2d;
+
+mod testbed;
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+struct SegmentsTestbed {
+    world: World,
+}
+
+impl SegmentsTestbed {
+    pub fn new(config: &testbed::Config) -> SegmentsTestbed {
+        let window_width = config.window_width as f32 / config.pixels_per_unit;
+        let window_height = config.window_height as f32 / config.pixels_per_unit;
+        
+        let mut world = World::default();
+        
+        // Terrain polyline
+        let terrain = [
+            Vec2::new(-window_width / 2.0, 5.0),
+            Vec2::new(-20.0, -10.0),
+            Vec2::new(-5.0, -15.0),
+            Vec2::new(10.0, -20.0),
+            Vec2::new(25.0, -12.0),
+            Vec2::new(window_width / 2.0, 0.0),
+        ];
+        
+        for points in terrain.windows(2) {
+            let segment = shapes::Segment::new(points[0], points[1]);
+            
+            let mut body = Body::new(segment.into_shape(), 0.0, Material::new(0.4, 0.2));
+            body.set_static();
+            world.add_body(body);
+        }
+        
+        for i in 0..10 {
+            let shape = if i % 2 == 0 {
+                shapes::Circle::new(1.0).into_shape()
+            } else {
+                shapes::Polygon::rect(2.0, 2.0).into_shape()
+            };
+            
+            let mut body = Body::new(shape, 10.0, Material::new(0.3, 0.2));
+            body.transform.position = Vec2::new(-30.0 + 5.0 * i as f32, window_height / 2.0 - 5.0);
+            world.add_body(body);
+        }
+        
+        SegmentsTestbed {
+            world,
+        }
+    }
+}
+
+impl testbed::Testbed for SegmentsTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.left_mouse_released {
+            let polygon = shapes::Polygon::rect(3.0, 3.0);
+            
+            let mut body = Body::new(polygon.into_shape(), 10.0, Material::new(0.3, 0.2));
+            
+            body.transform.position = input.mouse_position;
+            body.transform.set_rotation(0.2);
+            
+            self.world.add_body(body);
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        for body in self.world.bodies_iter() {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", self.world.body_count()), 16);
+        
+        for contact in self.world.contacts() {
+            canvas.draw_point(contact.position);
+            canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+        }
+    }
+}
+
+fn main() {
+    let config = testbed::Config {
+        title: "Segments".to_string(),
+        window_width: 800,
+        window_height: 600,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = SegmentsTestbed::new(&config);
+    
+    testbed::run(testbed, config);
+}
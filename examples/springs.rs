@@ -20,8 +20,7 @@ impl SpringsTestbed {
         let ground_width = window_width / 2.0;
         let ground_height = window_height / 10.0;
     
-        let ground_vertices = box_vertices(ground_width, ground_height);
-        let ground_poly = shapes::Polygon::new(ground_vertices);
+        let ground_poly = shapes::Polygon::rect(ground_width, ground_height);
     
         let mut ground = Body::new(ground_poly.into_shape(), 10.0, Material::new(1.2, 0.2));
         ground.transform.position.y = -window_height / 2.0 + ground_height / 2.0 + 0.1;
@@ -31,8 +30,7 @@ impl SpringsTestbed {
         let box_width = 2.5;
         let box_height = 2.5;
         
-        let box_vertices = box_vertices(box_width, box_height);
-        let box_poly = shapes::Polygon::new(box_vertices);
+        let box_poly = shapes::Polygon::rect(box_width, box_height);
         
         let mut box_body = Body::new(box_poly.into_shape(), 500.0, Material::new(1.2, 0.2));
         box_body.transform.position.y = 10.0;
@@ -120,10 +118,6 @@ impl testbed::Testbed for SpringsTestbed {
     }
 }
 
-fn box_vertices(w: f32, h: f32) -> Vec<Vec2> {
-    vec![Vec2::ZERO, Vec2::RIGHT * w, Vec2::new(w, h), Vec2::UP * h]
-}
-
 fn main() {
     let config = testbed::Config {
         title: "Springs".to_string(),
@@ -0,0 +1,88 @@
+extern crate physics2d;
+
+mod testbed;
+
+use physics2d::*;
+
+use physics2d::debug::DebugCollision;
+
+struct TerrainTestbed {
+    world: World,
+}
+
+impl TerrainTestbed {
+    pub fn new(config: &testbed::Config) -> TerrainTestbed {
+        let window_width = config.window_width as f32 / config.pixels_per_unit;
+        let window_height = config.window_height as f32 / config.pixels_per_unit;
+        
+        let mut world = World::default();
+        
+        // Rolling hills, one sample per unit across the window
+        const SAMPLES: usize = 81;
+        let heights = (0..SAMPLES).map(|i| {
+            let x = i as f32 / (SAMPLES - 1) as f32;
+            (x * 12.0).sin() * 0.5 + (x * 31.0).sin() * 0.15 + 1.0
+        }).collect();
+        
+        let scale = Vec2::new(window_width / (SAMPLES - 1) as f32, 6.0);
+        let terrain = shapes::Heightfield::new(heights, scale);
+        
+        let mut ground = Body::new(terrain.into_shape(), 0.0, Material::new(0.3, 0.0));
+        ground.transform.position = Vec2::new(-window_width / 2.0, -window_height / 2.0);
+        ground.set_static();
+        world.add_body(ground);
+        
+        TerrainTestbed {
+            world,
+        }
+    }
+}
+
+impl testbed::Testbed for TerrainTestbed {
+    fn sfml_loop(&mut self, input: &testbed::Input, dt: f32) {
+        if input.left_mouse_released {
+            let polygon = shapes::Polygon::rect(2.0, 2.0);
+            
+            let mut body = Body::new(polygon.into_shape(), 10.0, Material::new(0.3, 0.0));
+            body.transform.position = input.mouse_position;
+            
+            self.world.add_body(body);
+        }
+        
+        if input.right_mouse_released {
+            let mut body = Body::new(shapes::Circle::new(1.0).into_shape(), 10.0, Material::new(0.3, 0.0));
+            body.transform.position = input.mouse_position;
+            
+            self.world.add_body(body);
+        }
+        
+        self.world.update(dt);
+    }
+    
+    fn sfml_draw(&mut self, canvas: &mut testbed::Canvas, dt: f32) {
+        for body in self.world.bodies_iter() {
+            canvas.draw_body(body);
+        }
+        
+        canvas.draw_text(format!("FPS: {}", 1.0 / dt), 16);
+        canvas.draw_text(format!("Body count: {}", self.world.body_count()), 16);
+        
+        for contact in self.world.contacts() {
+            canvas.draw_point(contact.position);
+            canvas.draw_line(contact.position, contact.position + contact.normal * contact.penetration)
+        }
+    }
+}
+
+fn main() {
+    let config = testbed::Config {
+        title: "Terrain".to_string(),
+        window_width: 800,
+        window_height: 600,
+        pixels_per_unit: 10.0,
+    };
+    
+    let testbed = TerrainTestbed::new(&config);
+    
+    testbed::run(testbed, config);
+}
@@ -86,14 +86,132 @@ impl Canvas {
         self.draw_queue.push(Box::new(vertex_array));
     }
     
+    fn draw_ellipse(&mut self, _sfml_pos: sfml::system::Vector2f,
+                    transform: &Transform,
+                    ellipse: &shapes::Ellipse) {
+        const POINT_COUNT: u32 = 30;
+        
+        let mut vertex_array = sfml::graphics::VertexArray::default();
+        vertex_array.set_primitive_type(sfml::graphics::PrimitiveType::LineStrip);
+        
+        for i in 0..POINT_COUNT {
+            let angle = 2.0f32 * math::PI * i as f32 / POINT_COUNT as f32;
+            let p = Vec2::new(angle.cos() * ellipse.half_extents.x, angle.sin() * ellipse.half_extents.y);
+            let sfml_vertex = sfml::graphics::Vertex::new(
+                sfml_vec2(transform.world_pos(&p), self.pixels_per_unit),
+                sfml::graphics::Color::CYAN,
+                sfml::system::Vector2f::new(0.0, 0.0)
+            );
+            vertex_array.append(&sfml_vertex);
+        }
+        
+        // A line from the center shows the rotation
+        let first_vertex = vertex_array[0];
+        vertex_array.append(&first_vertex);
+        vertex_array.append(&sfml::graphics::Vertex::new(
+            sfml_vec2(transform.position, self.pixels_per_unit),
+            sfml::graphics::Color::CYAN,
+            sfml::system::Vector2f::new(0.0, 0.0)
+        ));
+        
+        self.draw_queue.push(Box::new(vertex_array));
+    }
+    
     fn draw_polygon(&mut self, _sfml_pos: sfml::system::Vector2f,
                     transform: &Transform,
                     polygon: &shapes::Polygon) {
+        
+        const CORNER_POINT_COUNT: u32 = 5;
+        
+        let mut vertex_array = sfml::graphics::VertexArray::default();
+        vertex_array.set_primitive_type(sfml::graphics::PrimitiveType::LineStrip);
+        
+        for (i, vertex) in polygon.vertices.iter().enumerate() {
+            // Rounded corners are arcs from the normal of the previous face to that of the next
+            let prev_normal = polygon.normals[(i + polygon.vert_count() - 1) % polygon.vert_count()];
+            let normal = polygon.normals[i];
+            
+            let start_angle = prev_normal.y.atan2(prev_normal.x);
+            let arc_angle = prev_normal.cross(normal).atan2(prev_normal.dot(&normal));
+            
+            let point_count = if polygon.radius > 0.0 { CORNER_POINT_COUNT } else { 0 };
+            
+            for j in 0..=point_count {
+                let angle = start_angle + arc_angle * j as f32 / CORNER_POINT_COUNT as f32;
+                let p = *vertex + Vec2::new(angle.cos(), angle.sin()) * polygon.radius;
+                let sfml_vertex = sfml::graphics::Vertex::new(
+                    sfml_vec2(transform.world_pos(&p), self.pixels_per_unit),
+                    // TODO: Parameter?
+                    sfml::graphics::Color::CYAN,
+                    sfml::system::Vector2f::new(0.0, 0.0)
+                );
+                vertex_array.append(&sfml_vertex);
+            }
+        }
+        
+        let first_vertex = vertex_array[0];
+        vertex_array.append(&first_vertex);
+        
+        self.draw_queue.push(Box::new(vertex_array));
+    }
+    
+    fn draw_capsule(&mut self, _sfml_pos: sfml::system::Vector2f,
+                    transform: &Transform,
+                    capsule: &shapes::Capsule) {
+        const CAP_POINT_COUNT: u32 = 15;
+        
+        let mut vertex_array = sfml::graphics::VertexArray::default();
+        vertex_array.set_primitive_type(sfml::graphics::PrimitiveType::LineStrip);
+        
+        let end_points = capsule.end_points();
+        
+        // Right cap from -90 to 90 degrees, then left cap from 90 to 270 degrees
+        for (cap, center) in [end_points[1], end_points[0]].iter().enumerate() {
+            for i in 0..=CAP_POINT_COUNT {
+                let angle = math::PI * (i as f32 / CAP_POINT_COUNT as f32 + cap as f32 - 0.5);
+                let p = *center + Vec2::new(angle.cos(), angle.sin()) * capsule.radius;
+                let sfml_vertex = sfml::graphics::Vertex::new(
+                    sfml_vec2(transform.world_pos(&p), self.pixels_per_unit),
+                    // TODO: Parameter?
+                    sfml::graphics::Color::CYAN,
+                    sfml::system::Vector2f::new(0.0, 0.0)
+                );
+                vertex_array.append(&sfml_vertex);
+            }
+        }
+        
+        let first_vertex = vertex_array[0];
+        vertex_array.append(&first_vertex);
+        
+        self.draw_queue.push(Box::new(vertex_array));
+    }
+    
+    fn draw_segment(&mut self, _sfml_pos: sfml::system::Vector2f,
+                    transform: &Transform,
+                    segment: &shapes::Segment) {
+        let mut vertex_array = sfml::graphics::VertexArray::default();
+        vertex_array.set_primitive_type(sfml::graphics::PrimitiveType::Lines);
+        
+        for vertex in [segment.a, segment.b].iter() {
+            let sfml_vertex = sfml::graphics::Vertex::new(
+                sfml_vec2(transform.world_pos(vertex), self.pixels_per_unit),
+                // TODO: Parameter?
+                sfml::graphics::Color::CYAN,
+                sfml::system::Vector2f::new(0.0, 0.0)
+            );
+            vertex_array.append(&sfml_vertex);
+        }
+        
+        self.draw_queue.push(Box::new(vertex_array));
+    }
     
+    fn draw_chain(&mut self, _sfml_pos: sfml::system::Vector2f,
+                  transform: &Transform,
+                  chain: &shapes::Chain) {
         let mut vertex_array = sfml::graphics::VertexArray::default();
         vertex_array.set_primitive_type(sfml::graphics::PrimitiveType::LineStrip);
         
-        for vertex in polygon.vertices.iter() {
+        for vertex in chain.vertices().iter() {
             let sfml_vertex = sfml::graphics::Vertex::new(
                 sfml_vec2(transform.world_pos(vertex), self.pixels_per_unit),
                 // TODO: Parameter?
@@ -103,19 +221,48 @@ impl Canvas {
             vertex_array.append(&sfml_vertex);
         }
         
-        let first_vertex = vertex_array[0];
-        vertex_array.append(&first_vertex);
+        if chain.is_closed() {
+            let first_vertex = vertex_array[0];
+            vertex_array.append(&first_vertex);
+        }
         
         self.draw_queue.push(Box::new(vertex_array));
     }
     
-    pub fn draw_body(&mut self, body: &Body) {
-        let sfml_pos = sfml_vec2(body.transform.position, self.pixels_per_unit);
+    fn draw_heightfield(&mut self, _sfml_pos: sfml::system::Vector2f,
+                        transform: &Transform,
+                        heightfield: &shapes::Heightfield) {
+        let mut vertex_array = sfml::graphics::VertexArray::default();
+        vertex_array.set_primitive_type(sfml::graphics::PrimitiveType::LineStrip);
         
-        match body.shape {
-            shapes::Shape::Circle(ref circle) => self.draw_circle(sfml_pos, &body.transform, circle),
-            shapes::Shape::Polygon(ref polygon) => self.draw_polygon(sfml_pos, &body.transform, polygon),
-        };
+        for i in 0..heightfield.heights().len() {
+            let sfml_vertex = sfml::graphics::Vertex::new(
+                sfml_vec2(transform.world_pos(&heightfield.sample(i)), self.pixels_per_unit),
+                sfml::graphics::Color::CYAN,
+                sfml::system::Vector2f::new(0.0, 0.0)
+            );
+            vertex_array.append(&sfml_vertex);
+        }
+        
+        self.draw_queue.push(Box::new(vertex_array));
+    }
+    
+    pub fn draw_body(&mut self, body: &Body) {
+        for fixture in body.fixtures().iter() {
+            let transform = body.transform.world_transform(&fixture.transform);
+            let sfml_pos = sfml_vec2(transform.position, self.pixels_per_unit);
+            
+            match fixture.shape {
+                shapes::Shape::Circle(ref circle) => self.draw_circle(sfml_pos, &transform, circle),
+                shapes::Shape::Polygon(ref polygon) => self.draw_polygon(sfml_pos, &transform, polygon),
+                shapes::Shape::Capsule(ref capsule) => self.draw_capsule(sfml_pos, &transform, capsule),
+                shapes::Shape::Ellipse(ref ellipse) => self.draw_ellipse(sfml_pos, &transform, ellipse),
+                shapes::Shape::Segment(ref segment) => self.draw_segment(sfml_pos, &transform, segment),
+                shapes::Shape::Chain(ref chain) => self.draw_chain(sfml_pos, &transform, chain),
+                shapes::Shape::Convex(ref convex) => self.draw_polygon(sfml_pos, &transform, convex.outline()),
+                shapes::Shape::Heightfield(ref heightfield) => self.draw_heightfield(sfml_pos, &transform, heightfield),
+            };
+        }
     }
     
     pub fn draw_point(&mut self, point: Vec2) {
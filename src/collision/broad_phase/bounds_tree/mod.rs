@@ -6,7 +6,7 @@ mod tests;
 use crate::math::{Bounds, Vec2};
 use crate::util::pool;
 use crate::world::{Bodies, ConstraintsMap, BodyPair, Body, BodyId};
-use crate::collision::ContactConstraint;
+use crate::collision::ContactManifold;
 
 use std;
 use crate::collision::broad_phase::{BroadPhase, ProxyId};
@@ -22,7 +22,7 @@ impl Nullable for NodeId {
 }
 
 #[derive(Clone)]
-struct Node<T: Default> {
+pub(crate) struct Node<T: Default> {
     bounds: Bounds,
     
     parent: NodeId,
@@ -64,7 +64,8 @@ impl<T: Default> Node<T> {
     }
 }
 
-struct BoundsTree<T: Default> {
+#[derive(Clone)]
+pub(crate) struct BoundsTree<T: Default> {
     pool: pool::Pool<Node<T>>,
     root_id: NodeId,
 }
@@ -222,7 +223,7 @@ impl<T: Default> BoundsTree<T> {
     ///
     /// # Returns
     /// The `NodeId` of the inserted leaf node.
-    fn insert_leaf(&mut self, bounds: Bounds, data: T) -> NodeId {
+    pub(crate) fn insert_leaf(&mut self, bounds: Bounds, data: T) -> NodeId {
         let leaf_id = self.pool.allocate_with(Node::new(bounds, data));
         
         if self.root_id == NodeId::NULL {
@@ -345,6 +346,15 @@ impl<T: Default> BoundsTree<T> {
         self.pool.free(leaf_id);
     }
     
+    /// Calls `f` with the data of every leaf whose bounds intersect `bounds`.
+    pub(crate) fn for_each_intersecting<F>(&self, bounds: Bounds, mut f: F)
+        where F: FnMut(&T) {
+        self.query(bounds, |node| {
+            f(&node.data);
+            true
+        });
+    }
+    
     fn query<F>(&self, bounds: Bounds, mut f: F)
         where F: FnMut(&Node<T>) -> bool {
         let mut stack = Vec::with_capacity(self.pool.object_count);
@@ -370,21 +380,26 @@ impl<T: Default> BoundsTree<T> {
     }
 }
 
+/// Identifies a fixture by its body and its index in the body.
+type FixtureKey = (BodyId, usize);
+
+#[derive(Clone)]
 pub struct BoundsTreeBroadPhase {
-    tree: BoundsTree<BodyId>,
-    reinserted_bodies: Vec<BodyId>,
+    tree: BoundsTree<FixtureKey>,
+    /// Fixtures whose proxies were created or reinserted since the last update.
+    moved_fixtures: Vec<FixtureKey>,
 }
 
 impl BoundsTreeBroadPhase {
     pub fn new() -> BoundsTreeBroadPhase {
         BoundsTreeBroadPhase {
             tree: BoundsTree::new(),
-            reinserted_bodies: Vec::new(),
+            moved_fixtures: Vec::new(),
         }
     }
     
     pub fn post_update(&mut self) {
-        self.reinserted_bodies.clear();
+        self.moved_fixtures.clear();
     }
 }
 
@@ -392,20 +407,26 @@ const EXPANSION_FACTOR: f32 = 0.05;
 
 impl BroadPhase for BoundsTreeBroadPhase {
     fn new_potential_pairs(&self, bodies: &Bodies,
-                           constraints: &mut ConstraintsMap<ContactConstraint>) {
-        for body_id in self.reinserted_bodies.iter() {
-            let body = &bodies[*body_id];
-            
-            if body.is_static() {
-                continue;
-            }
+                           constraints: &mut ConstraintsMap<ContactManifold>) {
+        for &(body_id, fixture_idx) in self.moved_fixtures.iter() {
+            let body = &bodies[body_id];
+            let fixture = &body.fixtures()[fixture_idx];
             
-            self.tree.query(body.bounds, |node| {
-                if node.data == *body_id {
+            self.tree.query(fixture.bounds, |node| {
+                let (other_id, other_fixture_idx) = node.data;
+                
+                // Static bodies never collide with each other
+                if other_id == body_id || (body.is_static() && bodies[other_id].is_static()) {
+                    return true;
+                }
+                
+                let other_fixture = &bodies[other_id].fixtures()[other_fixture_idx];
+                
+                if !fixture.filter.should_collide(&other_fixture.filter) {
                     return true;
                 }
                 
-                let body_pair = BodyPair::new(node.data, *body_id);
+                let body_pair = BodyPair::new(other_id, body_id);
                 if !constraints.contains_key(&body_pair) {
                     constraints.insert(body_pair, Vec::new());
                 }
@@ -414,18 +435,22 @@ impl BroadPhase for BoundsTreeBroadPhase {
         }
     }
     
-    fn create_proxy(&mut self, body: &Body) -> ProxyId {
-        self.tree.insert_leaf(body.bounds.expand_by(EXPANSION_FACTOR), body.id)
+    fn create_proxy(&mut self, body: &Body, fixture_idx: usize) -> ProxyId {
+        let bounds = body.fixtures()[fixture_idx].bounds.expand_by(EXPANSION_FACTOR);
+        
+        self.moved_fixtures.push((body.id, fixture_idx));
+        
+        self.tree.insert_leaf(bounds, (body.id, fixture_idx))
     }
     
     fn destroy_proxy(&mut self, proxy_id: ProxyId) {
         self.tree.remove_leaf(proxy_id);
     }
     
-    fn update_proxy(&mut self, proxy_id: ProxyId, body: &Body) {
+    fn update_proxy(&mut self, proxy_id: ProxyId, body: &Body, fixture_idx: usize) {
         // TODO: Explore rotation based method instead
         
-        if self.tree.get_node(proxy_id).bounds.contains(&body.bounds) {
+        if self.tree.get_node(proxy_id).bounds.contains(&body.fixtures()[fixture_idx].bounds) {
             return;
         }
         
@@ -433,8 +458,6 @@ impl BroadPhase for BoundsTreeBroadPhase {
         
         self.destroy_proxy(proxy_id);
         
-        self.create_proxy(body);
-        
-        self.reinserted_bodies.push(body.id);
+        self.create_proxy(body, fixture_idx);
     }
 }
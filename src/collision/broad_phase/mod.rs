@@ -3,16 +3,18 @@ mod bounds_tree;
 
 pub use self::naive::NaiveBroadPhase;
 pub use self::bounds_tree::BoundsTreeBroadPhase;
+pub(crate) use self::bounds_tree::BoundsTree;
 
 use crate::world::{Bodies, ConstraintsMap, Body};
-use crate::collision::ContactConstraint;
+use crate::collision::ContactManifold;
 
 pub type ProxyId = usize;
 
 pub trait BroadPhase {
-    fn new_potential_pairs(&self, bodies: &Bodies, constraints: &mut ConstraintsMap<ContactConstraint>);
+    fn new_potential_pairs(&self, bodies: &Bodies, constraints: &mut ConstraintsMap<ContactManifold>);
     
-    fn create_proxy(&mut self, body: &Body) -> ProxyId;
+    /// Creates a proxy for the fixture of `body` with index `fixture_idx`.
+    fn create_proxy(&mut self, body: &Body, fixture_idx: usize) -> ProxyId;
     fn destroy_proxy(&mut self, proxy_id: ProxyId);
-    fn update_proxy(&mut self, proxy_id: ProxyId, body: &Body);
+    fn update_proxy(&mut self, proxy_id: ProxyId, body: &Body, fixture_idx: usize);
 }
@@ -1,11 +1,11 @@
 use crate::world::{Bodies, ConstraintsMap, BodyPair, Body};
-use crate::collision::ContactConstraint;
+use crate::collision::ContactManifold;
 use super::{BroadPhase, ProxyId};
 
 pub struct NaiveBroadPhase;
 
 impl BroadPhase for NaiveBroadPhase {
-    fn new_potential_pairs(&self, bodies: &Bodies, constraints: &mut ConstraintsMap<ContactConstraint>) {
+    fn new_potential_pairs(&self, bodies: &Bodies, constraints: &mut ConstraintsMap<ContactManifold>) {
         for body_a in bodies.iter() {
             for body_b in bodies.iter() {
                 let body_a_id = body_a.id;
@@ -28,11 +28,11 @@ impl BroadPhase for NaiveBroadPhase {
         }
     }
     
-    fn create_proxy(&mut self, _body: &Body) -> ProxyId {
+    fn create_proxy(&mut self, _body: &Body, _fixture_idx: usize) -> ProxyId {
         ProxyId::default()
     }
     
     fn destroy_proxy(&mut self, _proxy_id: ProxyId) {}
     
-    fn update_proxy(&mut self, _proxy_id: ProxyId, _body: &Body) {}
+    fn update_proxy(&mut self, _proxy_id: ProxyId, _body: &Body, _fixture_idx: usize) {}
 }
@@ -0,0 +1,483 @@
+use crate::math::{Vec2, Cross, Bounds};
+use crate::shapes::{Shape, Segment, Support, Matter};
+use crate::world::Transform;
+use super::narrow_phase::local_bounds;
+
+/// The maximum number of iterations of `distance`.
+const MAX_ITERATIONS: usize = 20;
+
+/// The minimum distance a new support point must add towards the origin for `distance` to keep
+/// iterating.
+const TOLERANCE: f32 = 1e-5;
+
+/// The separation of two shapes and their closest points, as computed by `distance`.
+#[derive(Copy, Clone, Debug)]
+pub struct DistanceOutput {
+    /// The distance between the shapes, or zero if they overlap.
+    pub distance: f32,
+    /// The point on the first shape closest to the second, in world space.
+    pub point_a: Vec2,
+    /// The point on the second shape closest to the first, in world space. If the shapes overlap,
+    /// this is the same as `point_a`.
+    pub point_b: Vec2,
+    /// The number of iterations taken.
+    pub iterations: usize,
+}
+
+/// A point of the Minkowski difference of two shapes, along with the support points it was built
+/// from.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct SimplexVertex {
+    /// The support point on the first shape, in world space.
+    pub a: Vec2,
+    /// The support point on the second shape, in world space.
+    pub b: Vec2,
+    /// `b - a`.
+    pub w: Vec2,
+    /// The barycentric coordinate of this vertex for the closest point to the origin.
+    pub weight: f32,
+}
+
+impl SimplexVertex {
+    /// Returns the support points of `a` and `b` for the direction `dir` from `a` to `b`, in world
+    /// space.
+    pub fn new<A, B>(a: &A, a_transform: &Transform, b: &B, b_transform: &Transform, dir: Vec2) -> SimplexVertex
+        where A: Support + ?Sized, B: Support + ?Sized {
+        let a = a_transform.world_pos(&a.support_point(&a_transform.local_dir(&-dir)));
+        let b = b_transform.world_pos(&b.support_point(&b_transform.local_dir(&dir)));
+        
+        SimplexVertex {
+            a,
+            b,
+            w: b - a,
+            weight: 1.0,
+        }
+    }
+}
+
+/// A point, segment or triangle in the Minkowski difference of two shapes.
+#[derive(Clone, Debug)]
+pub(super) struct Simplex {
+    pub vertices: [SimplexVertex; 3],
+    pub count: usize,
+}
+
+impl Simplex {
+    /// Reduces the simplex to the smallest sub-simplex containing the point closest to the origin,
+    /// and sets the weights of its vertices to the barycentric coordinates of that point.
+    fn solve(&mut self) {
+        match self.count {
+            1 => self.vertices[0].weight = 1.0,
+            2 => self.solve2(),
+            3 => self.solve3(),
+            _ => unreachable!(),
+        }
+    }
+    
+    fn solve2(&mut self) {
+        let (w1, w2) = (self.vertices[0].w, self.vertices[1].w);
+        let e12 = w2 - w1;
+        
+        // Region of w1
+        let d12_2 = -w1.dot(&e12);
+        if d12_2 <= 0.0 {
+            self.vertices[0].weight = 1.0;
+            self.count = 1;
+            return;
+        }
+        
+        // Region of w2
+        let d12_1 = w2.dot(&e12);
+        if d12_1 <= 0.0 {
+            self.vertices[0] = self.vertices[1];
+            self.vertices[0].weight = 1.0;
+            self.count = 1;
+            return;
+        }
+        
+        // Region of the edge
+        let inv_d12 = 1.0 / (d12_1 + d12_2);
+        self.vertices[0].weight = d12_1 * inv_d12;
+        self.vertices[1].weight = d12_2 * inv_d12;
+        self.count = 2;
+    }
+    
+    fn solve3(&mut self) {
+        let (w1, w2, w3) = (self.vertices[0].w, self.vertices[1].w, self.vertices[2].w);
+        
+        // Barycentric coordinates of the origin on each edge, before normalization
+        let e12 = w2 - w1;
+        let (d12_1, d12_2) = (w2.dot(&e12), -w1.dot(&e12));
+        
+        let e13 = w3 - w1;
+        let (d13_1, d13_2) = (w3.dot(&e13), -w1.dot(&e13));
+        
+        let e23 = w3 - w2;
+        let (d23_1, d23_2) = (w3.dot(&e23), -w2.dot(&e23));
+        
+        // Barycentric coordinates of the origin in the triangle, before normalization
+        let n123 = e12.cross(e13);
+        let d123_1 = n123 * w2.cross(w3);
+        let d123_2 = n123 * w3.cross(w1);
+        let d123_3 = n123 * w1.cross(w2);
+        
+        // Region of w1
+        if d12_2 <= 0.0 && d13_2 <= 0.0 {
+            self.vertices[0].weight = 1.0;
+            self.count = 1;
+            return;
+        }
+        
+        // Region of e12
+        if d12_1 > 0.0 && d12_2 > 0.0 && d123_3 <= 0.0 {
+            let inv_d12 = 1.0 / (d12_1 + d12_2);
+            self.vertices[0].weight = d12_1 * inv_d12;
+            self.vertices[1].weight = d12_2 * inv_d12;
+            self.count = 2;
+            return;
+        }
+        
+        // Region of e13
+        if d13_1 > 0.0 && d13_2 > 0.0 && d123_2 <= 0.0 {
+            let inv_d13 = 1.0 / (d13_1 + d13_2);
+            self.vertices[0].weight = d13_1 * inv_d13;
+            self.vertices[2].weight = d13_2 * inv_d13;
+            self.vertices[1] = self.vertices[2];
+            self.count = 2;
+            return;
+        }
+        
+        // Region of w2
+        if d12_1 <= 0.0 && d23_2 <= 0.0 {
+            self.vertices[0] = self.vertices[1];
+            self.vertices[0].weight = 1.0;
+            self.count = 1;
+            return;
+        }
+        
+        // Region of w3
+        if d13_1 <= 0.0 && d23_1 <= 0.0 {
+            self.vertices[0] = self.vertices[2];
+            self.vertices[0].weight = 1.0;
+            self.count = 1;
+            return;
+        }
+        
+        // Region of e23
+        if d23_1 > 0.0 && d23_2 > 0.0 && d123_1 <= 0.0 {
+            let inv_d23 = 1.0 / (d23_1 + d23_2);
+            self.vertices[1].weight = d23_1 * inv_d23;
+            self.vertices[2].weight = d23_2 * inv_d23;
+            self.vertices[0] = self.vertices[2];
+            self.count = 2;
+            return;
+        }
+        
+        // The origin is inside the triangle
+        let inv_d123 = 1.0 / (d123_1 + d123_2 + d123_3);
+        self.vertices[0].weight = d123_1 * inv_d123;
+        self.vertices[1].weight = d123_2 * inv_d123;
+        self.vertices[2].weight = d123_3 * inv_d123;
+    }
+    
+    /// Returns the direction from the simplex towards the origin.
+    fn search_direction(&self) -> Vec2 {
+        match self.count {
+            1 => -self.vertices[0].w,
+            2 => {
+                let e12 = self.vertices[1].w - self.vertices[0].w;
+                
+                // The perpendicular of the edge on the side of the origin
+                if e12.cross(-self.vertices[0].w) > 0.0 {
+                    -e12.cross(1.0)
+                } else {
+                    e12.cross(1.0)
+                }
+            },
+            _ => unreachable!(),
+        }
+    }
+    
+    /// Returns the point of the simplex closest to the origin.
+    fn closest_point(&self) -> Vec2 {
+        self.vertices[..self.count].iter().fold(Vec2::ZERO, |sum, v| sum + v.w * v.weight)
+    }
+    
+    /// Returns the closest points on the two shapes, in world space.
+    pub fn closest_points(&self) -> (Vec2, Vec2) {
+        let vertices = &self.vertices[..self.count];
+        
+        let a = vertices.iter().fold(Vec2::ZERO, |sum, v| sum + v.a * v.weight);
+        let b = vertices.iter().fold(Vec2::ZERO, |sum, v| sum + v.b * v.weight);
+        
+        (a, b)
+    }
+}
+
+/// Runs GJK on the cores of `a` and `b`, returning the final simplex and the number of iterations.
+///
+/// If the cores overlap, the simplex is a triangle containing the origin, or a point or segment
+/// through it.
+pub(super) fn gjk<A, B>(a: &A, a_transform: &Transform, b: &B, b_transform: &Transform) -> (Simplex, usize)
+    where A: Support + ?Sized, B: Support + ?Sized {
+    let dir = b_transform.position - a_transform.position;
+    let dir = if dir.sqr_len() > 0.0 { dir } else { Vec2::RIGHT };
+    
+    let first = SimplexVertex::new(a, a_transform, b, b_transform, dir);
+    
+    let mut simplex = Simplex {
+        vertices: [first; 3],
+        count: 1,
+    };
+    
+    let mut iterations = 0;
+    
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+        
+        simplex.solve();
+        
+        if simplex.count == 3 {
+            break;
+        }
+        
+        // The origin is on the simplex
+        if simplex.closest_point().sqr_len() < TOLERANCE * TOLERANCE {
+            break;
+        }
+        
+        let dir = simplex.search_direction();
+        let dir_len = dir.len();
+        
+        let vertex = SimplexVertex::new(a, a_transform, b, b_transform, dir);
+        
+        // The new vertex must bring the simplex closer to the origin, or the closest point is found
+        if (vertex.w - simplex.vertices[0].w).dot(&dir) <= TOLERANCE * dir_len {
+            break;
+        }
+        
+        simplex.vertices[simplex.count] = vertex;
+        simplex.count += 1;
+    }
+    
+    (simplex, iterations)
+}
+
+/// Computes the distance between two convex shapes and their closest points.
+///
+/// The distance is found with the GJK algorithm between the cores of the shapes (the vertices of a
+/// polygon, or the center of a circle), and then reduced by their radii. If the shapes overlap, the
+/// distance is zero and both closest points are a point between them.
+///
+/// # Examples
+/// ```
+/// # use physics2d::{Vec2, Transform, distance, shapes::{Circle, Polygon}};
+/// let circle = Circle::new(0.5);
+/// let square = Polygon::rect(2.0, 2.0);
+///
+/// let output = distance(&circle, &Transform::new(Vec2::new(3.0, 0.0), 0.0),
+///                       &square, &Transform::new(Vec2::ZERO, 0.0));
+///
+/// assert!((output.distance - 1.5).abs() < 1e-5);
+/// assert!((output.point_a - Vec2::new(2.5, 0.0)).len() < 1e-5);
+/// assert!((output.point_b.x - 1.0).abs() < 1e-5);
+/// ```
+pub fn distance<A, B>(a: &A, a_transform: &Transform, b: &B, b_transform: &Transform) -> DistanceOutput
+    where A: Support + ?Sized, B: Support + ?Sized {
+    let (simplex, iterations) = gjk(a, a_transform, b, b_transform);
+    
+    let (mut point_a, mut point_b) = simplex.closest_points();
+    
+    let core_distance = if simplex.count == 3 { 0.0 } else { (point_b - point_a).len() };
+    let (radius_a, radius_b) = (a.radius(), b.radius());
+    
+    let distance = if core_distance > radius_a + radius_b && core_distance > TOLERANCE {
+        // Move the points from the cores to the surfaces
+        let normal = (point_b - point_a) / core_distance;
+        point_a += normal * radius_a;
+        point_b -= normal * radius_b;
+        
+        core_distance - radius_a - radius_b
+    } else {
+        let point = (point_a + point_b) * 0.5;
+        point_a = point;
+        point_b = point;
+        
+        0.0
+    };
+    
+    DistanceOutput {
+        distance,
+        point_a,
+        point_b,
+        iterations,
+    }
+}
+
+/// Computes the distance between two shapes of any kind, splitting chains and heightfields into
+/// their segments.
+///
+/// A part of one shape outside the bounds of the other grown by some margin is further than the
+/// margin from it, so only the parts within a margin of the other shape are measured. The margin
+/// starts at the gap between the bounds of the shapes, and grows until it is at least the closest
+/// distance found, which is then exact. Terrain far from the other shape is never measured.
+pub(crate) fn distance_shapes(a: &Shape, a_transform: &Transform, b: &Shape, b_transform: &Transform) -> DistanceOutput {
+    let (a_bounds, b_bounds) = (a.bounds(Some(a_transform)), b.bounds(Some(b_transform)));
+    
+    // Bounds grown by this margin contain the bounds of both shapes, and so every part
+    let max_margin = 2.0 * a_bounds.union(&b_bounds).extents().len();
+    let min_growth = f32::min(a_bounds.extents().len(), b_bounds.extents().len());
+    
+    let offset = a_bounds.center() - b_bounds.center();
+    let extents = a_bounds.extents() + b_bounds.extents();
+    let mut margin = Vec2::new(offset.x.abs() - extents.x, offset.y.abs() - extents.y).max(&Vec2::ZERO).len();
+    
+    loop {
+        // Non-finite bounds never bound the parts, so every part is measured
+        if margin >= max_margin || margin.is_nan() || !max_margin.is_finite() {
+            return closest_parts(a, a_transform, None, b, b_transform, None).unwrap();
+        }
+        
+        let closest = closest_parts(a, a_transform, Some(&grow(&b_bounds, margin)),
+                                    b, b_transform, Some(&grow(&a_bounds, margin)));
+        
+        margin = match closest {
+            Some(closest) if closest.distance <= margin => return closest,
+            Some(closest) => closest.distance,
+            None => {
+                let grown = f32::max(2.0 * margin, margin + min_growth);
+                if grown > margin { grown } else { max_margin }
+            },
+        };
+    }
+}
+
+/// Returns the closest pair of convex parts of two shapes, measuring only the parts of each shape
+/// that intersect the given world bounds, or `None` if either shape has no part in them.
+fn closest_parts(a: &Shape, a_transform: &Transform, a_near: Option<&Bounds>,
+                 b: &Shape, b_transform: &Transform, b_near: Option<&Bounds>) -> Option<DistanceOutput> {
+    let mut closest: Option<DistanceOutput> = None;
+    
+    for_each_convex_part(a, a_transform, a_near, |part_a| {
+        for_each_convex_part(b, b_transform, b_near, |part_b| {
+            let output = distance(part_a, a_transform, part_b, b_transform);
+            
+            closest = match closest {
+                Some(closest) if closest.distance <= output.distance => Some(closest),
+                _ => Some(output),
+            };
+        });
+    });
+    
+    closest
+}
+
+/// Returns `bounds` grown by `margin` on every side.
+fn grow(bounds: &Bounds, margin: f32) -> Bounds {
+    Bounds::center_extents(bounds.center(), bounds.extents() + Vec2::ONE * margin)
+}
+
+/// Calls `f` with every convex part of `shape`, relative to the shape. The segments of chains and
+/// heightfields are skipped unless they intersect `near`, which is in world space.
+fn for_each_convex_part<F>(shape: &Shape, transform: &Transform, near: Option<&Bounds>, mut f: F)
+    where F: FnMut(&dyn Support) {
+    match *shape {
+        Shape::Circle(ref circle) => f(circle),
+        Shape::Polygon(ref polygon) => f(polygon),
+        Shape::Capsule(ref capsule) => f(capsule),
+        Shape::Ellipse(ref ellipse) => f(ellipse),
+        Shape::Segment(ref segment) => f(segment),
+        Shape::Convex(ref convex) => f(convex),
+        Shape::Chain(ref chain) => match near {
+            Some(near) => chain.for_each_segment_in(local_bounds(near, transform), |&i| {
+                let segment = chain.segment(i);
+                f(&Segment::new(segment.a, segment.b));
+            }),
+            None => for i in 0..chain.segment_count() {
+                let segment = chain.segment(i);
+                f(&Segment::new(segment.a, segment.b));
+            },
+        },
+        Shape::Heightfield(ref heightfield) => {
+            let cells = match near {
+                Some(near) => heightfield.cells_in(&local_bounds(near, transform)),
+                None => 0..heightfield.cell_count(),
+            };
+            
+            for i in cells {
+                let segment = heightfield.segment(i);
+                f(&Segment::new(segment.a, segment.b));
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Circle, Polygon, Capsule, Chain, Heightfield};
+    
+    #[test]
+    fn distance_polygons() {
+        let square = Polygon::rect(2.0, 2.0);
+        
+        // Corner to face, with the second square rotated by 45 degrees
+        let rotated = Transform::new(Vec2::new(4.0, 0.5), crate::math::PI / 4.0);
+        let output = distance(&square, &Transform::new(Vec2::ZERO, 0.0), &square, &rotated);
+        
+        let corner_x = 4.0 - 2.0f32.sqrt();
+        assert!((output.distance - (corner_x - 1.0)).abs() < 1e-4);
+        assert!((output.point_a - Vec2::new(1.0, 0.5)).len() < 1e-4);
+        assert!((output.point_b - Vec2::new(corner_x, 0.5)).len() < 1e-4);
+        
+        // Overlapping
+        let output = distance(&square, &Transform::new(Vec2::ZERO, 0.0), &square, &Transform::new(Vec2::new(1.5, 0.3), 0.2));
+        assert_eq!(output.distance, 0.0);
+        assert_eq!(output.point_a, output.point_b);
+    }
+    
+    #[test]
+    fn distance_rounded_shapes() {
+        let capsule = Capsule::new(1.0, 0.25);
+        let circle = Circle::new(0.5);
+        
+        // Above the middle of the capsule
+        let output = distance(&capsule, &Transform::new(Vec2::ZERO, 0.0), &circle, &Transform::new(Vec2::new(0.3, 2.0), 0.0));
+        assert!((output.distance - 1.25).abs() < 1e-5);
+        assert!((output.point_a - Vec2::new(0.3, 0.25)).len() < 1e-5);
+        assert!((output.point_b - Vec2::new(0.3, 1.5)).len() < 1e-5);
+        
+        // The radii overlap, but the cores do not
+        let output = distance(&capsule, &Transform::new(Vec2::ZERO, 0.0), &circle, &Transform::new(Vec2::new(0.0, 0.5), 0.0));
+        assert_eq!(output.distance, 0.0);
+        assert!((output.point_a - Vec2::new(0.0, 0.25)).len() < 1e-5);
+    }
+    
+    #[test]
+    fn distance_shapes_matches_every_part() {
+        let heights = (0..200).map(|i| (i as f32 * 0.3).sin()).collect();
+        let terrain = Heightfield::new(heights, Vec2::new(0.5, 2.0)).into_shape();
+        let cave = Chain::new_loop((0..64).map(|i| {
+            let angle = 2.0 * crate::math::PI * i as f32 / 64.0;
+            Vec2::new(angle.cos(), angle.sin()) * 20.0
+        }).collect()).into_shape();
+        
+        let ball = Circle::new(0.5).into_shape();
+        let block = Polygon::rect(1.0, 2.0).into_shape();
+        
+        let origin = Transform::new(Vec2::new(-10.0, -1.0), 0.1);
+        let positions = [Vec2::new(0.0, 5.0), Vec2::new(30.0, 0.0), Vec2::new(-40.0, 60.0),
+            Vec2::new(120.0, -5.0), Vec2::new(10.0, 1.5)];
+        
+        for &(a, b) in [(&terrain, &ball), (&block, &terrain), (&cave, &ball), (&cave, &block), (&terrain, &cave)].iter() {
+            for &position in positions.iter() {
+                let transform = Transform::new(position, 0.4);
+                
+                let output = distance_shapes(a, &origin, b, &transform);
+                let expected = closest_parts(a, &origin, None, b, &transform, None).unwrap();
+                
+                assert!((output.distance - expected.distance).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -1,12 +1,52 @@
 pub mod broad_phase;
 
 mod narrow_phase;
+mod distance;
 mod solver;
 
 pub use self::narrow_phase::{Collide, collide};
-pub use self::solver::{ContactConstraint};
+pub use self::solver::{ContactManifold};
+pub use self::distance::{DistanceOutput, distance};
+pub(crate) use self::distance::distance_shapes;
 use crate::math::{Vec2, Cross};
 
+/// The kind of feature of a shape that a contact point was created from.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FeatureType {
+    Vertex,
+    Face,
+}
+
+/// The features of the two shapes that a contact point was created from.
+///
+/// A contact created from the same features as a contact of the previous step is the same contact,
+/// even if its point moved, so the impulses accumulated for it can be reused.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ContactFeature {
+    /// The index of the feature of the first shape.
+    pub index_a: usize,
+    /// The index of the feature of the second shape.
+    pub index_b: usize,
+    pub type_a: FeatureType,
+    pub type_b: FeatureType,
+}
+
+impl ContactFeature {
+    pub fn new(index_a: usize, type_a: FeatureType, index_b: usize, type_b: FeatureType) -> ContactFeature {
+        ContactFeature {
+            index_a,
+            index_b,
+            type_a,
+            type_b,
+        }
+    }
+    
+    /// Returns the feature with the roles of the two shapes swapped.
+    pub fn flipped(self) -> ContactFeature {
+        ContactFeature::new(self.index_b, self.type_b, self.index_a, self.type_a)
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Contact {
     pub position: Vec2,
@@ -14,6 +54,15 @@ pub struct Contact {
     
     pub normal: Vec2,
     pub tangent: Vec2,
+    
+    /// The index of the fixture of the first body of the contact.
+    pub fixture_a: usize,
+    /// The index of the fixture of the second body of the contact.
+    pub fixture_b: usize,
+    
+    /// The features of the shapes the contact was created from, if the contact was clipped from
+    /// faces of polygons. Other contacts are identified by their position.
+    pub feature: Option<ContactFeature>,
 }
 
 impl Contact {
@@ -23,6 +72,15 @@ impl Contact {
             penetration,
             normal,
             tangent: normal.cross(1.0),
+            fixture_a: 0,
+            fixture_b: 0,
+            feature: None,
         }
     }
+    
+    /// Returns this contact, identified by `feature`.
+    pub fn with_feature(mut self, feature: ContactFeature) -> Contact {
+        self.feature = Some(feature);
+        self
+    }
 }
@@ -0,0 +1,14 @@
+use super::Collide;
+use super::rounded_polygon::{RoundedPolygon, collide_rounded_polygons};
+use crate::shapes::{Capsule};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide for Capsule {
+    fn collide(&self, self_transform: &Transform, other: &Capsule, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let self_poly = RoundedPolygon::from_capsule(self, self_transform);
+        let other_poly = RoundedPolygon::from_capsule(other, other_transform);
+        
+        collide_rounded_polygons(&self_poly, &other_poly)
+    }
+}
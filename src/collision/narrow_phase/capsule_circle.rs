@@ -0,0 +1,22 @@
+use super::{Collide, flip_contacts};
+use super::rounded_polygon::collide_rounded_segment_circle;
+use crate::math::{Vec2};
+use crate::shapes::{Circle, Capsule};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Circle> for Capsule {
+    fn collide(&self, self_transform: &Transform, other: &Circle, other_transform: &Transform) -> Option<Vec<Contact>> {
+        
+        let [a, b] = self.end_points();
+        
+        collide_rounded_segment_circle(self_transform.world_pos(&a), self_transform.world_pos(&b), self.radius,
+                                       self_transform.world_dir(&Vec2::UP), other, other_transform.position)
+    }
+}
+
+impl Collide<Capsule> for Circle {
+    fn collide(&self, self_transform: &Transform, other: &Capsule, other_transform: &Transform) -> Option<Vec<Contact>> {
+        flip_contacts(other.collide(other_transform, self, self_transform))
+    }
+}
@@ -0,0 +1,23 @@
+use super::Collide;
+use super::rounded_polygon::{RoundedPolygon, collide_rounded_polygons};
+use crate::shapes::{Capsule, Polygon};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Polygon> for Capsule {
+    fn collide(&self, self_transform: &Transform, other: &Polygon, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let self_poly = RoundedPolygon::from_capsule(self, self_transform);
+        let other_poly = RoundedPolygon::from_polygon(other, other_transform);
+        
+        collide_rounded_polygons(&self_poly, &other_poly)
+    }
+}
+
+impl Collide<Capsule> for Polygon {
+    fn collide(&self, self_transform: &Transform, other: &Capsule, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let self_poly = RoundedPolygon::from_polygon(self, self_transform);
+        let other_poly = RoundedPolygon::from_capsule(other, other_transform);
+        
+        collide_rounded_polygons(&self_poly, &other_poly)
+    }
+}
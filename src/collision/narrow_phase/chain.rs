@@ -0,0 +1,183 @@
+use super::rounded_polygon::{RoundedPolygon, collide_rounded_polygons, collide_rounded_polygons_with_reference,
+                             collide_rounded_segment_circle};
+use crate::math::{Vec2, Cross, Bounds};
+use super::convex::collide_convex;
+use crate::shapes::{Chain, ChainSegment, Circle, Segment, Support};
+use crate::world::Transform;
+use crate::collision::Contact;
+
+/// How far the contact normal may deviate from a segment's normal, as `1 - cos(angle)`, to still be
+/// treated as a face contact.
+const FACE_NORMAL_TOLERANCE: f32 = 0.001;
+
+/// The minimum cross product of the directions of two adjacent segments for the corner between
+/// them to be treated as convex rather than flat.
+const CONVEX_CORNER_TOLERANCE: f32 = 0.001;
+
+/// Collides every segment of `chain` near `other_bounds` using `collide_segment`, which is passed
+/// each segment in world space.
+pub(super) fn collide_chain<F>(chain: &Chain, transform: &Transform, other_bounds: &Bounds,
+                               mut collide_segment: F) -> Option<Vec<Contact>>
+    where F: FnMut(&ChainSegment) -> Option<Vec<Contact>> {
+    let mut contacts = Vec::new();
+    
+    chain.for_each_segment_in(local_bounds(other_bounds, transform), |&index| {
+        let segment = chain.segment(index).into_world_segment(transform);
+        
+        // The features of a segment are not unique within the whole shape, so its contacts are
+        // identified by their position
+        if let Some(segment_contacts) = collide_segment(&segment) {
+            contacts.extend(segment_contacts.into_iter().map(|contact| Contact { feature: None, ..contact }));
+        }
+    });
+    
+    if contacts.is_empty() {
+        None
+    } else {
+        Some(contacts)
+    }
+}
+
+/// Collides a chain segment, in world space, with a circle. Contacts whose normals point into a
+/// neighbouring segment are discarded; that segment collides with the circle instead.
+pub(super) fn collide_chain_segment_circle(segment: &ChainSegment,
+                                           circle: &Circle, circle_center: Vec2) -> Option<Vec<Contact>> {
+    let side_normal = side_normal(segment, circle_center);
+    
+    let contacts = collide_rounded_segment_circle(segment.a, segment.b, 0.0, side_normal, circle, circle_center)?;
+    
+    if contacts.iter().all(|contact| is_admissible(segment, side_normal, contact)) {
+        Some(contacts)
+    } else {
+        None
+    }
+}
+
+/// Collides a chain segment, in world space, with a rounded polygon. If a contact normal is close
+/// to the segment's normal or points into a neighbouring segment, the segment's face is used as the
+/// reference face instead.
+pub(super) fn collide_chain_segment_polygon(segment: &ChainSegment,
+                                            other: &RoundedPolygon) -> Option<Vec<Contact>> {
+    let side_normal = side_normal(segment, other.center());
+    
+    // Face 0 of the segment polygon, from its first to its second vertex, has the side normal
+    let (a, b) = if side_normal.dot(&(segment.b - segment.a).cross(1.0)) >= 0.0 {
+        (segment.a, segment.b)
+    } else {
+        (segment.b, segment.a)
+    };
+    
+    let segment_poly = RoundedPolygon::from_segment_points(a, b, side_normal, 0.0);
+    
+    let contacts = collide_rounded_polygons(&segment_poly, other)?;
+    
+    // A polygon face resting on the segment must not tilt the normal, or bodies sliding across the
+    // chain slowly drift sideways
+    let keep = contacts.iter().all(|contact| {
+        !is_face_normal(side_normal, contact) && is_admissible(segment, side_normal, contact)
+    });
+    
+    if keep {
+        return Some(contacts);
+    }
+    
+    collide_rounded_polygons_with_reference(&segment_poly, 0, other)
+}
+
+/// Collides a chain segment, in world space, with a convex shape given by its support mapping,
+/// where `center` is a point inside the shape. Contacts whose normals point into a neighbouring
+/// segment are discarded.
+pub(super) fn collide_chain_segment_convex<S>(segment: &ChainSegment, other: &S, other_transform: &Transform,
+                                              center: Vec2) -> Option<Vec<Contact>>
+    where S: Support + ?Sized {
+    let side_normal = side_normal(segment, center);
+    
+    let segment_shape = Segment::new(segment.a, segment.b);
+    let contacts = collide_convex(&segment_shape, &Transform::new(Vec2::ZERO, 0.0), other, other_transform)?;
+    
+    if contacts.iter().all(|contact| is_admissible(segment, side_normal, contact)) {
+        Some(contacts)
+    } else {
+        None
+    }
+}
+
+/// Returns the normal of `segment` on the side of `point`.
+fn side_normal(segment: &ChainSegment, point: Vec2) -> Vec2 {
+    let normal = (segment.b - segment.a).normalized().cross(1.0);
+    
+    if normal.dot(&(point - segment.a)) >= 0.0 { normal } else { -normal }
+}
+
+/// Whether the contact normal is close enough to `side_normal` to be treated as a face contact.
+fn is_face_normal(side_normal: Vec2, contact: &Contact) -> bool {
+    contact.normal.dot(&side_normal) >= 1.0 - FACE_NORMAL_TOLERANCE
+}
+
+/// Whether the contact normal lies within the normals the segment can produce on the side with
+/// normal `side_normal` without pointing into a neighbouring segment.
+///
+/// Face normals are always admissible. Other (vertex) normals are only admissible at the open ends
+/// of the chain, or at corners that are convex on the side of the contact, where the normal must lie
+/// between the normals of the two segments.
+fn is_admissible(segment: &ChainSegment, side_normal: Vec2, contact: &Contact) -> bool {
+    let edge = segment.b - segment.a;
+    
+    // 1 on the right of the segment, -1 on the left
+    let side = if side_normal.dot(&edge.cross(1.0)) >= 0.0 { 1.0 } else { -1.0 };
+    
+    if is_face_normal(side_normal, contact) {
+        return true;
+    }
+    
+    let at_a = (contact.position - segment.a).dot(&edge) < 0.5 * edge.sqr_len();
+    
+    let (adjacent_edge, turn) = match (at_a, segment.prev, segment.next) {
+        (true, Some(prev), _) => {
+            let adjacent_edge = segment.a - prev;
+            (adjacent_edge, adjacent_edge.normalized().cross(edge.normalized()))
+        },
+        (false, _, Some(next)) => {
+            let adjacent_edge = next - segment.b;
+            (adjacent_edge, edge.normalized().cross(adjacent_edge.normalized()))
+        },
+        // Open end of the chain
+        _ => return true,
+    };
+    
+    // Turning left makes the corner convex on the right side, and vice versa
+    if turn * side <= CONVEX_CORNER_TOLERANCE {
+        return false;
+    }
+    
+    let adjacent_normal = adjacent_edge.normalized().cross(1.0) * side;
+    
+    // The cone spans less than half a turn; the normal must be on the inside of both its sides
+    let orientation = adjacent_normal.cross(side_normal);
+    
+    adjacent_normal.cross(contact.normal) * orientation >= 0.0 &&
+        contact.normal.cross(side_normal) * orientation >= 0.0
+}
+
+/// Returns the bounds, relative to `transform`, of `bounds` in world space.
+pub(crate) fn local_bounds(bounds: &Bounds, transform: &Transform) -> Bounds {
+    let (center, extents) = (bounds.center(), bounds.extents());
+    
+    let corners = [
+        center + extents,
+        center + Vec2::new(extents.x, -extents.y),
+        center - extents,
+        center + Vec2::new(-extents.x, extents.y),
+    ];
+    
+    let mut min = transform.local_pos(&corners[0]);
+    let mut max = min;
+    
+    for corner in corners.iter().skip(1) {
+        let corner = transform.local_pos(corner);
+        min = min.min(&corner);
+        max = max.max(&corner);
+    }
+    
+    Bounds::new(min, max)
+}
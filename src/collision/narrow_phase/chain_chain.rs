@@ -0,0 +1,24 @@
+use super::Collide;
+use crate::shapes::{Chain, Segment};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+/// Chains have no mass, so they never need to be collided with each other.
+impl Collide for Chain {
+    fn collide(&self, _self_transform: &Transform, _other: &Chain, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
+
+/// Chains and segments have no mass, so they never need to be collided with each other.
+impl Collide<Segment> for Chain {
+    fn collide(&self, _self_transform: &Transform, _other: &Segment, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
+
+impl Collide<Chain> for Segment {
+    fn collide(&self, _self_transform: &Transform, _other: &Chain, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
@@ -0,0 +1,20 @@
+use super::{Collide, flip_contacts};
+use super::chain::{collide_chain, collide_chain_segment_circle};
+use crate::shapes::{Chain, Circle, Matter};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Circle> for Chain {
+    fn collide(&self, self_transform: &Transform, other: &Circle, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let center = other_transform.position;
+        
+        collide_chain(self, self_transform, &other.bounds(Some(other_transform)),
+                      |segment| collide_chain_segment_circle(segment, other, center))
+    }
+}
+
+impl Collide<Chain> for Circle {
+    fn collide(&self, self_transform: &Transform, other: &Chain, other_transform: &Transform) -> Option<Vec<Contact>> {
+        flip_contacts(other.collide(other_transform, self, self_transform))
+    }
+}
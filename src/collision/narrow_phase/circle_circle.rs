@@ -1,12 +1,12 @@
 use super::Collide;
 use crate::shapes::{Circle};
-use crate::world::{Body};
+use crate::world::Transform;
 use crate::collision::{Contact};
 
 impl Collide for Circle {
-    fn collide(&self, self_body: &Body, other: &Circle, other_body: &Body) -> Option<Vec<Contact>> {
+    fn collide(&self, self_transform: &Transform, other: &Circle, other_transform: &Transform) -> Option<Vec<Contact>> {
         let r = self.radius + other.radius;
-        let normal = other_body.transform.position - self_body.transform.position;
+        let normal = other_transform.position - self_transform.position;
         
         if normal.sqr_len() > r * r {
             return None;
@@ -14,7 +14,7 @@ impl Collide for Circle {
         
         let distance = normal.len();
         let normal = normal / distance;
-        let contact_position = normal * self.radius + self_body.transform.position;
+        let contact_position = normal * self.radius + self_transform.position;
         
         let contact = Contact::new(contact_position, r - distance, normal);
         
@@ -1,12 +1,12 @@
-use super::{Collide};
+use super::{Collide, flip_contacts};
 use crate::math::{Vec2, clamp01};
 use crate::shapes::{Circle, Polygon};
-use crate::world::{Body, Transform};
+use crate::world::Transform;
 use crate::collision::{Contact};
 
 impl Circle {
     /// Returns the face for which the penetration of the circle is least, the penetration and the corresponding
-    /// support point on the circle.
+    /// support point on the circle, with the circle inflated by the radius of the polygon.
     fn least_penetration_support_point(&self, self_transform: &Transform,
                                        other: &Polygon, other_transform: &Transform) -> (usize, f32, Vec2) {
         use std::f32::INFINITY;
@@ -23,7 +23,7 @@ impl Circle {
             let vertex = other.vertices[i];
             
             // Point on self furthest below the face
-            let support = self_local_pos - normal * self.radius;
+            let support = self_local_pos - normal * (self.radius + other.radius);
             
             // Penetration wrt this face is negative of the distance of support from this face
             let penetration = -normal.dot(&(support - vertex));
@@ -40,9 +40,7 @@ impl Circle {
 }
 
 impl Collide<Polygon> for Circle {
-    fn collide(&self, self_body: &Body, other: &Polygon, other_body: &Body) -> Option<Vec<Contact>> {
-        let self_transform = &self_body.transform;
-        let other_transform = &other_body.transform;
+    fn collide(&self, self_transform: &Transform, other: &Polygon, other_transform: &Transform) -> Option<Vec<Contact>> {
         
         let (face_idx, mut penetration, support) =
             self.least_penetration_support_point(self_transform, other, other_transform);
@@ -68,41 +66,31 @@ impl Collide<Polygon> for Circle {
         let rel_contact_point = contact_point - self_transform.position;
         let contact_dist_sqr = rel_contact_point.sqr_len();
         
-        if contact_dist_sqr > self.radius * self.radius {
+        let radius = self.radius + other.radius;
+        
+        if contact_dist_sqr > radius * radius {
             return None;
         }
         
         if corner_contact {
-            penetration = self.radius - contact_dist_sqr.sqrt()
+            penetration = radius - contact_dist_sqr.sqrt()
         }
         
         let normal = if corner_contact {
-            rel_contact_point / (self.radius - penetration)
+            rel_contact_point / (radius - penetration)
         } else {
             -other_transform.world_dir(&face.normal)
         };
         
-        let contact = Contact::new(contact_point, penetration, normal);
+        // The contact is on the surface of the polygon, outside its core
+        let contact = Contact::new(contact_point - normal * other.radius, penetration, normal);
         
         Some(vec![contact])
     }
 }
 
 impl Collide<Circle> for Polygon {
-    fn collide(&self, self_body: &Body, other: &Circle, other_body: &Body) -> Option<Vec<Contact>> {
-        let contacts = other.collide(other_body, self, self_body);
-        
-        if contacts.is_none() {
-            contacts
-        } else {
-            // Normal must always point from self to other
-            let mut contacts = contacts.unwrap();
-            
-            for contact in contacts.iter_mut() {
-                contact.normal = -contact.normal;
-            }
-            
-            Some(contacts)
-        }
+    fn collide(&self, self_transform: &Transform, other: &Circle, other_transform: &Transform) -> Option<Vec<Contact>> {
+        flip_contacts(other.collide(other_transform, self, self_transform))
     }
-}
\ No newline at end of file
+}
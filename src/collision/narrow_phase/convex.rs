@@ -0,0 +1,340 @@
+use super::{Collide, flip_contacts};
+use super::chain::{collide_chain, collide_chain_segment_convex};
+use super::heightfield::collide_heightfield;
+use super::rounded_polygon::{RoundedPolygon, collide_rounded_polygons_with_reference};
+use crate::math::{Vec2, Cross, Mat2, clamp01};
+use crate::shapes::{Support, Matter, Convex, Ellipse, Circle, Polygon, Capsule, Segment, Chain,
+                    Heightfield};
+use crate::world::Transform;
+use crate::collision::Contact;
+use crate::collision::distance::{SimplexVertex, gjk};
+
+/// Distance between the cores below which they are treated as touching, and the precision of EPA.
+const TOLERANCE: f32 = 1e-5;
+
+/// The maximum number of vertices EPA adds to the polytope.
+const MAX_EPA_ITERATIONS: usize = 32;
+
+/// The angle by which the contact normal is rotated either way to look for a face on each shape.
+/// Faces whose normals are further than this from the contact normal are treated as vertices.
+const FACE_ANGLE: f32 = 0.01;
+
+/// Collides two convex shapes given by their support mappings, using GJK while their cores are
+/// separated and EPA once they overlap. The contact normals point from `a` to `b`.
+///
+/// When both shapes have a face along the contact normal, the faces are clipped against each other
+/// like polygons, giving two contacts; otherwise there is a single contact between the deepest
+/// points of the shapes.
+pub(super) fn collide_convex<A, B>(a: &A, a_transform: &Transform, b: &B, b_transform: &Transform) -> Option<Vec<Contact>>
+    where A: Support + ?Sized, B: Support + ?Sized {
+    let (simplex, _) = gjk(a, a_transform, b, b_transform);
+    
+    let (radius_a, radius_b) = (a.radius(), b.radius());
+    let radius = radius_a + radius_b;
+    
+    let (core_a, core_b) = simplex.closest_points();
+    let core_distance = (core_b - core_a).len();
+    
+    let (normal, separation, core_a, core_b) = if simplex.count < 3 && core_distance > TOLERANCE {
+        if core_distance > radius {
+            return None;
+        }
+        
+        ((core_b - core_a) / core_distance, core_distance, core_a, core_b)
+    } else {
+        let polytope = simplex.vertices[..simplex.count].to_vec();
+        let (normal, depth, core_a, core_b) = epa(a, a_transform, b, b_transform, polytope)?;
+        
+        (normal, -depth, core_a, core_b)
+    };
+    
+    let face_a = support_face(a, a_transform, normal);
+    let face_b = support_face(b, b_transform, -normal);
+    
+    if let (Some(face_a), Some(face_b)) = (face_a, face_b) {
+        let ref_poly = face_poly(face_a, normal, radius_a);
+        let inc_poly = face_poly(face_b, -normal, radius_b);
+        
+        // The faces may not overlap, in which case their ends touch at a single point. The faces are
+        // sampled anew each step, so their features do not identify the contacts
+        if let Some(contacts) = collide_rounded_polygons_with_reference(&ref_poly, 0, &inc_poly) {
+            return Some(contacts.into_iter().map(|contact| Contact { feature: None, ..contact }).collect());
+        }
+    }
+    
+    // Contact at the midpoint of the overlapping surfaces
+    let surface_a = core_a + normal * radius_a;
+    let surface_b = core_b - normal * radius_b;
+    
+    Some(vec![Contact::new((surface_a + surface_b) * 0.5, radius - separation, normal)])
+}
+
+/// Expands `polytope`, a simplex of the Minkowski difference of the cores of `a` and `b` that
+/// contains the origin, until it finds the edge of the difference closest to the origin.
+///
+/// Returns the contact normal from `a` to `b`, the penetration of the cores along it and the
+/// deepest points of the cores, or `None` if the cores are degenerate and touch without
+/// overlapping.
+fn epa<A, B>(a: &A, a_transform: &Transform, b: &B, b_transform: &Transform,
+             mut polytope: Vec<SimplexVertex>) -> Option<(Vec2, f32, Vec2, Vec2)>
+    where A: Support + ?Sized, B: Support + ?Sized {
+    let support = |dir: Vec2| SimplexVertex::new(a, a_transform, b, b_transform, dir);
+    
+    // A point or a segment through the origin is grown into a triangle
+    if polytope.len() == 1 {
+        let vertex = [Vec2::RIGHT, Vec2::LEFT, Vec2::UP, Vec2::DOWN].iter()
+            .map(|&dir| support(dir))
+            .find(|v| (v.w - polytope[0].w).sqr_len() > TOLERANCE * TOLERANCE)?;
+        
+        polytope.push(vertex);
+    }
+    
+    if polytope.len() == 2 {
+        let edge = polytope[1].w - polytope[0].w;
+        let (left, right) = (support(-edge.cross(1.0)), support(edge.cross(1.0)));
+        
+        // The side further from the edge, so that the triangle has an area
+        let distance = |v: &SimplexVertex| (v.w - polytope[0].w).cross(edge).abs();
+        let vertex = if distance(&left) >= distance(&right) { left } else { right };
+        
+        if distance(&vertex) <= TOLERANCE * edge.len() {
+            return None;
+        }
+        
+        polytope.push(vertex);
+    }
+    
+    // Counter-clockwise, so that the outward normal of each edge is to its right
+    if (polytope[1].w - polytope[0].w).cross(polytope[2].w - polytope[0].w) < 0.0 {
+        polytope.swap(1, 2);
+    }
+    
+    let mut closest = (0, Vec2::ZERO, 0.0);
+    
+    for _ in 0..MAX_EPA_ITERATIONS {
+        let n = polytope.len();
+        
+        closest = (0, Vec2::ZERO, f32::INFINITY);
+        
+        for i in 0..n {
+            let edge = polytope[(i + 1) % n].w - polytope[i].w;
+            
+            // A vertex added twice has no normal
+            if edge.sqr_len() <= TOLERANCE * TOLERANCE {
+                continue;
+            }
+            
+            let edge_normal = edge.cross(1.0).normalized();
+            let distance = edge_normal.dot(&polytope[i].w);
+            
+            if distance < closest.2 {
+                closest = (i, edge_normal, distance);
+            }
+        }
+        
+        if closest.2 == f32::INFINITY {
+            return None;
+        }
+        
+        let (i, edge_normal, distance) = closest;
+        let vertex = support(edge_normal);
+        
+        if vertex.w.dot(&edge_normal) - distance <= TOLERANCE {
+            break;
+        }
+        
+        polytope.insert(i + 1, vertex);
+    }
+    
+    let (i, edge_normal, distance) = closest;
+    let (v1, v2) = (polytope[i], polytope[(i + 1) % polytope.len()]);
+    
+    // Barycentric coordinates of the projection of the origin on the closest edge
+    let edge = v2.w - v1.w;
+    let t = if edge.sqr_len() > 0.0 { clamp01(-v1.w.dot(&edge) / edge.sqr_len()) } else { 0.0 };
+    
+    let core_a = v1.a + (v2.a - v1.a) * t;
+    let core_b = v1.b + (v2.b - v1.b) * t;
+    
+    // Moving `b` by `-distance * edge_normal` separates the cores
+    Some((-edge_normal, distance, core_a, core_b))
+}
+
+/// Returns the end points, in world space, of the face of the core of `shape` whose normal is
+/// within `FACE_ANGLE` of `normal`, if it has one.
+fn support_face<S>(shape: &S, transform: &Transform, normal: Vec2) -> Option<(Vec2, Vec2)>
+    where S: Support + ?Sized {
+    let support = |dir: Vec2| transform.world_pos(&shape.support_point(&transform.local_dir(&dir)));
+    
+    let point = support(normal);
+    let left = support(Mat2::rotation(FACE_ANGLE) * normal);
+    let right = support(Mat2::rotation(-FACE_ANGLE) * normal);
+    
+    let face = right - left;
+    let is_same = |p: Vec2, q: Vec2| (p - q).sqr_len() <= TOLERANCE * TOLERANCE;
+    
+    // A smooth boundary has a different support point in every direction, while a face is swept
+    // by its end points
+    if is_same(left, right) || !(is_same(point, left) || is_same(point, right)) {
+        return None;
+    }
+    
+    if face.normalized().dot(&normal).abs() > FACE_ANGLE.sin() * 2.0 {
+        return None;
+    }
+    
+    Some((left, right))
+}
+
+/// Returns the two-sided rounded polygon of a face whose normal is close to `normal`.
+fn face_poly(face: (Vec2, Vec2), normal: Vec2, radius: f32) -> RoundedPolygon {
+    let (a, b) = face;
+    let face_normal = (b - a).normalized().cross(1.0);
+    
+    // Face 0, from its first to its second vertex, must face along `normal`
+    if face_normal.dot(&normal) >= 0.0 {
+        RoundedPolygon::from_segment_points(a, b, face_normal, radius)
+    } else {
+        RoundedPolygon::from_segment_points(b, a, -face_normal, radius)
+    }
+}
+
+/// Implements `Collide` between a support-mapped shape and convex shapes with the generic narrow
+/// phase.
+macro_rules! impl_collide_convex {
+    ($mapped:ty: $($shape:ty),*) => {
+        impl Collide for $mapped {
+            fn collide(&self, self_transform: &Transform, other: &$mapped, other_transform: &Transform) -> Option<Vec<Contact>> {
+                collide_convex(self, self_transform, other, other_transform)
+            }
+        }
+        
+        $(
+            impl Collide<$shape> for $mapped {
+                fn collide(&self, self_transform: &Transform, other: &$shape, other_transform: &Transform) -> Option<Vec<Contact>> {
+                    collide_convex(self, self_transform, other, other_transform)
+                }
+            }
+            
+            impl Collide<$mapped> for $shape {
+                fn collide(&self, self_transform: &Transform, other: &$mapped, other_transform: &Transform) -> Option<Vec<Contact>> {
+                    collide_convex(self, self_transform, other, other_transform)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Collide` between a support-mapped shape and chains and heightfields, colliding each
+/// nearby segment with the generic narrow phase.
+macro_rules! impl_collide_segments_convex {
+    ($mapped:ty) => {
+        impl Collide<$mapped> for Chain {
+            fn collide(&self, self_transform: &Transform, other: &$mapped, other_transform: &Transform) -> Option<Vec<Contact>> {
+                let center = other_transform.world_pos(&other.center_of_mass());
+                
+                collide_chain(self, self_transform, &other.bounds(Some(other_transform)),
+                              |segment| collide_chain_segment_convex(segment, other, other_transform, center))
+            }
+        }
+        
+        impl Collide<Chain> for $mapped {
+            fn collide(&self, self_transform: &Transform, other: &Chain, other_transform: &Transform) -> Option<Vec<Contact>> {
+                flip_contacts(other.collide(other_transform, self, self_transform))
+            }
+        }
+        
+        impl Collide<$mapped> for Heightfield {
+            fn collide(&self, self_transform: &Transform, other: &$mapped, other_transform: &Transform) -> Option<Vec<Contact>> {
+                let center = other_transform.world_pos(&other.center_of_mass());
+                
+                collide_heightfield(self, self_transform, &other.bounds(Some(other_transform)),
+                                    |segment| collide_chain_segment_convex(segment, other, other_transform, center))
+            }
+        }
+        
+        impl Collide<Heightfield> for $mapped {
+            fn collide(&self, self_transform: &Transform, other: &Heightfield, other_transform: &Transform) -> Option<Vec<Contact>> {
+                flip_contacts(other.collide(other_transform, self, self_transform))
+            }
+        }
+    };
+}
+
+impl_collide_convex!(Convex: Circle, Polygon, Capsule, Segment);
+impl_collide_convex!(Ellipse: Circle, Polygon, Capsule, Segment, Convex);
+
+impl_collide_segments_convex!(Convex);
+impl_collide_segments_convex!(Ellipse);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn collide_convex_matches_polygons() {
+        let ground = Polygon::rect(10.0, 1.0);
+        let square = Polygon::rect(1.0, 1.0);
+        
+        let ground_transform = Transform::new(Vec2::ZERO, 0.0);
+        let square_transform = Transform::new(Vec2::new(0.3, 0.95), 0.0);
+        
+        let expected = ground.collide(&ground_transform, &square, &square_transform).unwrap();
+        let contacts = collide_convex(&ground, &ground_transform, &square, &square_transform).unwrap();
+        
+        assert_eq!(contacts.len(), 2);
+        
+        for (contact, expected) in contacts.iter().zip(expected.iter()) {
+            assert!((contact.normal - expected.normal).len() < 1e-5);
+            assert!((contact.penetration - expected.penetration).abs() < 1e-5);
+        }
+        
+        // Separated cores within the radius of a circle, above a corner of the square
+        let circle = Circle::new(0.5);
+        let contacts = collide_convex(&square, &square_transform, &circle, &Transform::new(Vec2::new(1.1, 1.75), 0.0)).unwrap();
+        
+        let corner = Vec2::new(0.8, 1.45);
+        let expected_normal = (Vec2::new(1.1, 1.75) - corner).normalized();
+        
+        assert_eq!(contacts.len(), 1);
+        assert!((contacts[0].normal - expected_normal).len() < 1e-4);
+        assert!((contacts[0].penetration - (0.5 - 0.3 * 2.0f32.sqrt())).abs() < 1e-4);
+    }
+    
+    #[test]
+    fn collide_convex_deep_overlap() {
+        let square = Polygon::rect(2.0, 2.0);
+        let capsule = Capsule::new(0.5, 0.25);
+        
+        // The core of the capsule is inside the square, closest to its top face
+        let contacts = collide_convex(&square, &Transform::new(Vec2::ZERO, 0.0),
+                                      &capsule, &Transform::new(Vec2::new(0.0, 0.6), 0.0)).unwrap();
+        
+        assert_eq!(contacts.len(), 2);
+        
+        for contact in contacts.iter() {
+            assert!((contact.normal - Vec2::UP).len() < 1e-5);
+            assert!((contact.penetration - 0.65).abs() < 1e-4);
+        }
+    }
+    
+    #[test]
+    fn epa_skips_zero_length_edges() {
+        let (a, b) = (Polygon::rect(2.0, 2.0), Polygon::rect(2.0, 2.0));
+        let (a_transform, b_transform) = (Transform::new(Vec2::ZERO, 0.0), Transform::new(Vec2::new(0.0, 1.5), 0.0));
+        let support = |dir: Vec2| SimplexVertex::new(&a, &a_transform, &b, &b_transform, dir);
+        
+        // A counter-clockwise triangle around the origin with its first vertex added twice
+        let polytope = vec![
+            support(Vec2::new(-1.0, -1.0)),
+            support(Vec2::new(-1.0, -1.0)),
+            support(Vec2::new(1.0, -1.0)),
+            support(Vec2::UP),
+        ];
+        
+        let (normal, penetration, _, _) = epa(&a, &a_transform, &b, &b_transform, polytope).unwrap();
+        
+        assert!((normal - Vec2::UP).len() < 1e-5);
+        assert!((penetration - 0.5).abs() < 1e-5);
+    }
+}
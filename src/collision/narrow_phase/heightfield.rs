@@ -0,0 +1,29 @@
+use super::chain::local_bounds;
+use crate::math::Bounds;
+use crate::shapes::{Heightfield, ChainSegment};
+use crate::world::Transform;
+use crate::collision::Contact;
+
+/// Collides every cell of `heightfield` overlapping `other_bounds` using `collide_segment`, which
+/// is passed the surface of each cell in world space.
+pub(super) fn collide_heightfield<F>(heightfield: &Heightfield, transform: &Transform, other_bounds: &Bounds,
+                                     mut collide_segment: F) -> Option<Vec<Contact>>
+    where F: FnMut(&ChainSegment) -> Option<Vec<Contact>> {
+    let mut contacts = Vec::new();
+    
+    for index in heightfield.cells_in(&local_bounds(other_bounds, transform)) {
+        let segment = heightfield.segment(index).into_world_segment(transform);
+        
+        // The features of a segment are not unique within the whole shape, so its contacts are
+        // identified by their position
+        if let Some(segment_contacts) = collide_segment(&segment) {
+            contacts.extend(segment_contacts.into_iter().map(|contact| Contact { feature: None, ..contact }));
+        }
+    }
+    
+    if contacts.is_empty() {
+        None
+    } else {
+        Some(contacts)
+    }
+}
@@ -0,0 +1,22 @@
+use super::{Collide, flip_contacts};
+use super::chain::collide_chain_segment_polygon;
+use super::heightfield::collide_heightfield;
+use super::rounded_polygon::RoundedPolygon;
+use crate::shapes::{Heightfield, Capsule, Matter};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Capsule> for Heightfield {
+    fn collide(&self, self_transform: &Transform, other: &Capsule, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let other_poly = RoundedPolygon::from_capsule(other, other_transform);
+        
+        collide_heightfield(self, self_transform, &other.bounds(Some(other_transform)),
+                            |segment| collide_chain_segment_polygon(segment, &other_poly))
+    }
+}
+
+impl Collide<Heightfield> for Capsule {
+    fn collide(&self, self_transform: &Transform, other: &Heightfield, other_transform: &Transform) -> Option<Vec<Contact>> {
+        flip_contacts(other.collide(other_transform, self, self_transform))
+    }
+}
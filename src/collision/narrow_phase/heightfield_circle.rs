@@ -0,0 +1,21 @@
+use super::{Collide, flip_contacts};
+use super::chain::collide_chain_segment_circle;
+use super::heightfield::collide_heightfield;
+use crate::shapes::{Heightfield, Circle, Matter};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Circle> for Heightfield {
+    fn collide(&self, self_transform: &Transform, other: &Circle, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let center = other_transform.position;
+        
+        collide_heightfield(self, self_transform, &other.bounds(Some(other_transform)),
+                            |segment| collide_chain_segment_circle(segment, other, center))
+    }
+}
+
+impl Collide<Heightfield> for Circle {
+    fn collide(&self, self_transform: &Transform, other: &Heightfield, other_transform: &Transform) -> Option<Vec<Contact>> {
+        flip_contacts(other.collide(other_transform, self, self_transform))
+    }
+}
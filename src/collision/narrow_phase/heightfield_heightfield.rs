@@ -0,0 +1,37 @@
+use super::Collide;
+use crate::shapes::{Heightfield, Segment, Chain};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+/// Heightfields have no mass, so they never need to be collided with each other.
+impl Collide for Heightfield {
+    fn collide(&self, _self_transform: &Transform, _other: &Heightfield, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
+
+/// Heightfields and segments have no mass, so they never need to be collided with each other.
+impl Collide<Segment> for Heightfield {
+    fn collide(&self, _self_transform: &Transform, _other: &Segment, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
+
+impl Collide<Heightfield> for Segment {
+    fn collide(&self, _self_transform: &Transform, _other: &Heightfield, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
+
+/// Heightfields and chains have no mass, so they never need to be collided with each other.
+impl Collide<Chain> for Heightfield {
+    fn collide(&self, _self_transform: &Transform, _other: &Chain, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
+
+impl Collide<Heightfield> for Chain {
+    fn collide(&self, _self_transform: &Transform, _other: &Heightfield, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
@@ -1,27 +1,113 @@
 mod circle_circle;
 mod polygon_polygon;
 mod circle_polygon;
+mod capsule_circle;
+mod capsule_polygon;
+mod capsule_capsule;
+mod segment_circle;
+mod segment_polygon;
+mod segment_capsule;
+mod segment_segment;
+mod chain_circle;
+mod chain_polygon;
+mod chain_capsule;
+mod chain_chain;
+mod chain;
+mod heightfield_circle;
+mod heightfield_polygon;
+mod heightfield_capsule;
+mod heightfield_heightfield;
+mod heightfield;
+mod convex;
+mod rounded_polygon;
+
+pub(crate) use self::chain::local_bounds;
 
 use crate::math::{Vec2};
 use crate::shapes::{Shape, Polygon};
 use crate::world::{Body, Transform};
-use crate::collision::Contact;
+use crate::collision::{Contact, ContactFeature, FeatureType};
 
 pub trait Collide<T = Self> {
-    fn collide(&self, self_body: &Body, other: &T, other_body: &Body) -> Option<Vec<Contact>>;
+    /// Collides this shape, placed in world space by `self_transform`, with `other`, placed by
+    /// `other_transform`. Contact normals point from this shape to `other`.
+    fn collide(&self, self_transform: &Transform, other: &T, other_transform: &Transform) -> Option<Vec<Contact>>;
 }
 
+/// Collides every pair of fixtures of `a` and `b` whose bounds overlap and whose filters allow
+/// them to collide. Contact normals point from `a` to `b`.
 pub fn collide(a: &Body, b: &Body) -> Option<Vec<Contact>> {
-    match b.shape {
+    let mut contacts = Vec::new();
+    
+    for (a_idx, fixture_a) in a.fixtures().iter().enumerate() {
+        for (b_idx, fixture_b) in b.fixtures().iter().enumerate() {
+            if !fixture_a.bounds.intersects(&fixture_b.bounds) || !fixture_a.filter.should_collide(&fixture_b.filter) {
+                continue;
+            }
+            
+            let transform_a = a.transform.world_transform(&fixture_a.transform);
+            let transform_b = b.transform.world_transform(&fixture_b.transform);
+            
+            if let Some(fixture_contacts) = collide_shapes(&fixture_a.shape, &transform_a, &fixture_b.shape, &transform_b) {
+                contacts.extend(fixture_contacts.into_iter().map(|mut contact| {
+                    contact.fixture_a = a_idx;
+                    contact.fixture_b = b_idx;
+                    contact
+                }));
+            }
+        }
+    }
+    
+    if contacts.is_empty() {
+        None
+    } else {
+        Some(contacts)
+    }
+}
+
+fn collide_shapes(a: &Shape, a_transform: &Transform, b: &Shape, b_transform: &Transform) -> Option<Vec<Contact>> {
+    match *b {
         Shape::Circle(ref circle) => {
-            match_fn_to_shape!(a.shape, collide(a, circle, b))
+            match_fn_to_shape!(*a, collide(a_transform, circle, b_transform))
         },
         Shape::Polygon(ref polygon) => {
-            match_fn_to_shape!(a.shape, collide(a, polygon, b))
+            match_fn_to_shape!(*a, collide(a_transform, polygon, b_transform))
+        },
+        Shape::Capsule(ref capsule) => {
+            match_fn_to_shape!(*a, collide(a_transform, capsule, b_transform))
+        },
+        Shape::Ellipse(ref ellipse) => {
+            match_fn_to_shape!(*a, collide(a_transform, ellipse, b_transform))
+        },
+        Shape::Segment(ref segment) => {
+            match_fn_to_shape!(*a, collide(a_transform, segment, b_transform))
+        },
+        Shape::Chain(ref chain) => {
+            match_fn_to_shape!(*a, collide(a_transform, chain, b_transform))
+        },
+        Shape::Heightfield(ref heightfield) => {
+            match_fn_to_shape!(*a, collide(a_transform, heightfield, b_transform))
+        },
+        Shape::Convex(ref convex) => {
+            match_fn_to_shape!(*a, collide(a_transform, convex, b_transform))
         },
     }
 }
 
+/// Reverses the normals of contacts computed with the shapes swapped, so that they point from
+/// self to other.
+fn flip_contacts(contacts: Option<Vec<Contact>>) -> Option<Vec<Contact>> {
+    contacts.map(|mut contacts| {
+        for contact in contacts.iter_mut() {
+            contact.normal = -contact.normal;
+            contact.tangent = -contact.tangent;
+            contact.feature = contact.feature.map(ContactFeature::flipped);
+        }
+        
+        contacts
+    })
+}
+
 // Utilities for polygon-polygon and circle-polygon intersection tests
 struct Face {
     a: Vec2,
@@ -49,6 +135,73 @@ impl Face {
     fn distance(&self, point: &Vec2) -> f32 {
         self.normal.dot(point) - self.normal.dot(&self.a)
     }
+    
+    /// Clips the segment between `points` to the part below this face. A point created by the clip
+    /// is identified by `clip_feature`.
+    fn clip_points_below(&self, points: &[ClipPoint; 2], clip_feature: ContactFeature) -> ([ClipPoint; 2], usize) {
+        let d1 = self.distance(&points[0].position);
+        let d2 = self.distance(&points[1].position);
+        
+        let mut clipped = *points;
+        let mut clip_count = 0usize;
+        
+        // If below face, leave as-is
+        if d1 <= 0.0 {
+            clipped[clip_count] = points[0];
+            clip_count += 1
+        }
+        
+        if d2 <= 0.0 {
+            clipped[clip_count] = points[1];
+            clip_count += 1;
+        }
+        
+        if clip_count >= 2 {
+            return (clipped, clip_count);
+        }
+        
+        // If one point above and other below face, clip
+        if d1 * d2 < 0.0 {
+            let t = d1 / (d1 - d2);
+            let position = points[0].position + (points[1].position - points[0].position) * t;
+            clipped[clip_count] = ClipPoint::new(position, clip_feature);
+            clip_count += 1;
+        }
+        
+        (clipped, clip_count)
+    }
+}
+
+/// A point of an incident face being clipped, with the features it was created from, relative to
+/// the reference and incident shapes.
+#[derive(Copy, Clone)]
+struct ClipPoint {
+    position: Vec2,
+    feature: ContactFeature,
+}
+
+impl ClipPoint {
+    fn new(position: Vec2, feature: ContactFeature) -> ClipPoint {
+        ClipPoint {
+            position,
+            feature,
+        }
+    }
+    
+    /// Returns the end points of the incident face at `inc_face_idx` of a shape with `inc_vert_count`
+    /// vertices, identified by the reference face and the incident vertices.
+    fn incident_points(inc_face: &Face, ref_face_idx: usize, inc_face_idx: usize, inc_vert_count: usize) -> [ClipPoint; 2] {
+        let vertex_feature = |index: usize| ContactFeature::new(ref_face_idx, FeatureType::Face, index, FeatureType::Vertex);
+        
+        [ClipPoint::new(inc_face.a, vertex_feature(inc_face_idx)),
+            ClipPoint::new(inc_face.b, vertex_feature((inc_face_idx + 1) % inc_vert_count))]
+    }
+    
+    /// Returns the feature of a point where the incident face at `inc_face_idx` is clipped by the side
+    /// plane through the reference vertex at `ref_vertex_idx`.
+    fn side_feature(ref_vertex_idx: usize, inc_face_idx: usize) -> ContactFeature {
+        ContactFeature::new(ref_vertex_idx, FeatureType::Vertex, inc_face_idx, FeatureType::Face)
+    }
 }
 
 impl Polygon {
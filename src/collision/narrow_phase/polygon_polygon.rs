@@ -1,52 +1,11 @@
-use super::{Collide, Face};
+use super::{Collide, ClipPoint};
+use super::rounded_polygon::{RoundedPolygon, collide_rounded_polygons};
 use crate::math::{Vec2};
-use crate::shapes::{Polygon};
-use crate::world::{Body, Transform};
+use crate::shapes::{Polygon, Support};
+use crate::world::Transform;
 use crate::collision::{Contact};
 
-impl Face {
-    fn clip_points_below(&self, points: &[Vec2; 2]) -> ([Vec2; 2], usize) {
-        let d1 = self.distance(&points[0]);
-        let d2 = self.distance(&points[1]);
-        
-        let mut clipped = [Vec2::ZERO; 2];
-        let mut clip_count = 0usize;
-        
-        // If below face, leave as-is
-        if d1 <= 0.0 {
-            clipped[clip_count] = points[0];
-            clip_count += 1
-        }
-        
-        if d2 <= 0.0 {
-            clipped[clip_count] = points[1];
-            clip_count += 1;
-        }
-        
-        if clip_count >= 2 {
-            return (clipped, clip_count);
-        }
-        
-        // If one point above and other below face, clip
-        if d1 * d2 < 0.0 {
-            let t = d1 / (d1 - d2);
-            clipped[clip_count] = points[0] + (points[1] - points[0]) * t;
-            clip_count += 1;
-        }
-        
-        (clipped, clip_count)
-    }
-}
-
 impl Polygon {
-    /// Returns the furthest point of the shape along `dir` (which is relative to this shape).
-    fn support_point(&self, dir: &Vec2) -> Vec2 {
-        *self.vertices.iter()
-            // a.dot(dir) returns the signed distance of the vertex a along dir
-             .max_by(|a, b| a.dot(dir).partial_cmp(&b.dot(dir)).unwrap())
-             .unwrap()
-    }
-    
     /// Returns the face of this polygon for which the penetration of the other polygon is least, and
     /// the penetration itself.
     ///
@@ -55,24 +14,57 @@ impl Polygon {
     /// (support point) in a direction opposite to the normal of a face lies _above_ the face, the whole shape lies
     /// above that face and its penetration for that face is negative.
     ///
+    /// The radii of the polygons are added to the penetration, as if the faces were moved out by them.
+    ///
     /// If the returned penetration is negative, the polygons do not intersect.
     fn least_penetration_face(&self, self_transform: &Transform,
                               other: &Polygon, other_transform: &Transform) -> (usize, f32) {
         use std::f32::INFINITY;
         
+        let radius = self.radius + other.radius;
+        
+        // Vertex and normal describing ith face of self, relative to other
+        let normal = |i: usize| other_transform.local_dir(&self_transform.world_dir(&self.normals[i]));
+        let vertex = |i: usize| other_transform.local_pos(&self_transform.world_pos(&self.vertices[i]));
+        
         let mut face_index = 0usize;
         let mut min_pen = INFINITY;
         
+        if self.is_rect() {
+            // Opposite faces of a rectangle share an axis, so a single projection of the other
+            // polygon onto the axis gives the penetration for both faces
+            let mut penetrations = [0.0f32; 4];
+            
+            for i in 0..2 {
+                let normal = normal(i);
+                
+                let (min_dist, max_dist) = other.vertices.iter()
+                    .map(|v| normal.dot(v))
+                    .fold((INFINITY, -INFINITY), |(min, max), dist| (min.min(dist), max.max(dist)));
+                
+                penetrations[i] = normal.dot(&vertex(i)) - min_dist + radius;
+                penetrations[i + 2] = max_dist - normal.dot(&vertex(i + 2)) + radius;
+            }
+            
+            for (i, &penetration) in penetrations.iter().enumerate() {
+                if penetration < min_pen {
+                    min_pen = penetration;
+                    face_index = i;
+                }
+            }
+            
+            return (face_index, min_pen);
+        }
+        
         for i in 0..self.vert_count() {
-            // Vertex and normal describing ith face of self, relative to other
-            let normal = other_transform.local_dir(&self_transform.world_dir(&self.normals[i]));
-            let vertex = other_transform.local_pos(&self_transform.world_pos(&self.vertices[i]));
+            let normal = normal(i);
+            let vertex = vertex(i);
             
             // Vertex of other which is furthest below the face
             let support = other.support_point(&-normal);
             
             // Penetration wrt this face is negative of the distance of support from this face
-            let penetration = -normal.dot(&(support - vertex));
+            let penetration = -normal.dot(&(support - vertex)) + radius;
             
             if penetration < min_pen {
                 min_pen = penetration;
@@ -83,7 +75,8 @@ impl Polygon {
         (face_index, min_pen)
     }
     
-    fn incident_face(&self, ref_face_normal: &Vec2) -> Face {
+    /// Returns the index of the face most anti-parallel to `ref_face_normal`.
+    fn incident_face(&self, ref_face_normal: &Vec2) -> usize {
         use std::f32::INFINITY;
         
         let mut min_dot = INFINITY;
@@ -98,14 +91,12 @@ impl Polygon {
             }
         }
         
-        self.face(inc_face_idx)
+        inc_face_idx
     }
 }
 
 impl Collide for Polygon {
-    fn collide(&self, self_body: &Body, other: &Polygon, other_body: &Body) -> Option<Vec<Contact>> {
-        let self_transform = &self_body.transform;
-        let other_transform = &other_body.transform;
+    fn collide(&self, self_transform: &Transform, other: &Polygon, other_transform: &Transform) -> Option<Vec<Contact>> {
         
         let (self_face_idx, self_pen) = self.least_penetration_face(self_transform, other, other_transform);
         
@@ -120,48 +111,54 @@ impl Collide for Polygon {
             return None;
         }
         
-        let (ref_poly, ref_body, ref_face_idx): (&Polygon, &Body, usize);
+        // Rounded corners may touch each other rather than a face, which the rounded polygon
+        // clipping handles
+        if self.radius > 0.0 || other.radius > 0.0 {
+            return collide_rounded_polygons(&RoundedPolygon::from_polygon(self, self_transform),
+                                            &RoundedPolygon::from_polygon(other, other_transform));
+        }
+        
+        let (ref_poly, ref_transform, ref_face_idx): (&Polygon, &Transform, usize);
         
-        let (inc_poly, inc_body): (&Polygon, &Body);
+        let (inc_poly, inc_transform): (&Polygon, &Transform);
         
         let self_is_ref_poly = other_pen >= 0.95 * self_pen + 0.01 * other_pen;
         
         if self_is_ref_poly {
             ref_poly = self;
-            ref_body = self_body;
+            ref_transform = self_transform;
             ref_face_idx = self_face_idx;
             
             inc_poly = other;
-            inc_body = other_body;
+            inc_transform = other_transform;
         } else {
             ref_poly = other;
-            ref_body = other_body;
+            ref_transform = other_transform;
             ref_face_idx = other_face_idx;
             
             inc_poly = self;
-            inc_body = self_body;
+            inc_transform = self_transform;
         }
         
-        let (ref_transform, inc_transform) = (&ref_body.transform, &inc_body.transform);
-        
         let ref_face = ref_poly.face(ref_face_idx).into_world_face(ref_transform);
         
-        let inc_face = inc_poly
-            .incident_face(&inc_transform.local_dir(&ref_face.normal))
-            .into_world_face(inc_transform);
+        let inc_face_idx = inc_poly.incident_face(&inc_transform.local_dir(&ref_face.normal));
+        let inc_face = inc_poly.face(inc_face_idx).into_world_face(inc_transform);
         
-        let mut inc_points = [inc_face.a, inc_face.b];
+        let mut inc_points = ClipPoint::incident_points(&inc_face, ref_face_idx, inc_face_idx, inc_poly.vert_count());
         
-        let side_faces_idx = [(ref_face_idx + 1) % ref_poly.vert_count(),
-            (ref_face_idx + ref_poly.vert_count() - 1) % ref_poly.vert_count()];
+        // Each side face passes through a vertex of the ref face, which identifies the points it clips
+        let next_vertex_idx = (ref_face_idx + 1) % ref_poly.vert_count();
+        let side_faces_idx = [(next_vertex_idx, next_vertex_idx),
+            ((ref_face_idx + ref_poly.vert_count() - 1) % ref_poly.vert_count(), ref_face_idx)];
         
         // Clip by side faces of ref face (not ref face itself)
-        for side_face_idx in side_faces_idx.iter() {
+        for &(side_face_idx, ref_vertex_idx) in side_faces_idx.iter() {
             // If less than 2 points clipped, floating point error, return
             let (clipped, clip_count) =
-                ref_poly.face(*side_face_idx)
+                ref_poly.face(side_face_idx)
                         .into_world_face(ref_transform)
-                        .clip_points_below(&inc_points);
+                        .clip_points_below(&inc_points, ClipPoint::side_feature(ref_vertex_idx, inc_face_idx));
             
             if clip_count < 2 {
                 return None;
@@ -174,15 +171,64 @@ impl Collide for Polygon {
         
         // For each incident point, create a contact
         let contacts = inc_points.iter().filter_map(|inc_point| {
-            let d = ref_face.distance(inc_point);
+            let d = ref_face.distance(&inc_point.position);
             // Only keep points behind the reference face
             if d > 0.0 {
                 None
             } else {
-                Some(Contact::new(*inc_point, -d, normal))
+                // Features are relative to the ref and inc polygons, flip them if self is inc
+                let feature = if self_is_ref_poly { inc_point.feature } else { inc_point.feature.flipped() };
+                Some(Contact::new(inc_point.position, -d, normal).with_feature(feature))
             }
         }).collect();
         
         Some(contacts)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{ContactFeature, FeatureType};
+    
+    fn features(contacts: &[Contact]) -> Vec<ContactFeature> {
+        contacts.iter().map(|contact| contact.feature.unwrap()).collect()
+    }
+    
+    #[test]
+    fn polygon_contact_features() {
+        let ground = Polygon::rect(10.0, 1.0);
+        let square = Polygon::rect(1.0, 1.0);
+        
+        let ground_transform = Transform::new(Vec2::ZERO, 0.0);
+        let square_transform = Transform::new(Vec2::new(0.3, 0.95), 0.0);
+        
+        let contacts = ground.collide(&ground_transform, &square, &square_transform).unwrap();
+        assert_eq!(contacts.len(), 2);
+        
+        // The vertices of the square resting on the ground face
+        for feature in features(&contacts).iter() {
+            assert_eq!((feature.type_a, feature.type_b), (FeatureType::Face, FeatureType::Vertex));
+        }
+        
+        // Sliding far along the ground keeps the features
+        let slid = ground.collide(&ground_transform, &square, &Transform::new(Vec2::new(2.3, 0.95), 0.0)).unwrap();
+        assert_eq!(features(&slid), features(&contacts));
+        
+        // Swapping the shapes swaps the features. The square's face becomes the reference face, and the
+        // contacts are clipped by the sides of the square at the same points
+        let swapped = features(&square.collide(&square_transform, &ground, &ground_transform).unwrap());
+        assert_eq!(swapped.len(), 2);
+        
+        for feature in features(&contacts).into_iter().map(ContactFeature::flipped) {
+            assert!(swapped.contains(&feature));
+        }
+        
+        // Overhanging the ground, one point is clipped by the side of the ground's top face
+        let overhang = ground.collide(&ground_transform, &square, &Transform::new(Vec2::new(5.3, 0.95), 0.0)).unwrap();
+        assert_eq!(overhang.len(), 2);
+        assert!(features(&overhang).iter().any(|feature| {
+            (feature.type_a, feature.type_b) == (FeatureType::Vertex, FeatureType::Face)
+        }));
+    }
+}
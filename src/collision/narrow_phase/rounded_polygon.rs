@@ -0,0 +1,341 @@
+use super::{Face, ClipPoint};
+use crate::math::{Vec2, Cross, clamp01};
+use crate::shapes::{Circle, Polygon, Capsule, Segment};
+use crate::world::Transform;
+use crate::collision::Contact;
+
+/// Separation of the polygon cores below which the polygons are treated as overlapping, and are
+/// clipped against each other instead of being tested for a vertex-vertex contact.
+const CORE_SEPARATION_TOLERANCE: f32 = 0.0005;
+
+/// A convex polygon inflated by a radius, in world space.
+///
+/// Capsules and segments are rounded polygons with two vertices and two opposite faces, a segment
+/// having no radius. This allows them to be collided with polygons and with each other using the
+/// same clipping as polygons.
+pub(super) struct RoundedPolygon {
+    vertices: Vec<Vec2>,
+    normals: Vec<Vec2>,
+    radius: f32,
+}
+
+impl RoundedPolygon {
+    pub fn from_polygon(polygon: &Polygon, transform: &Transform) -> RoundedPolygon {
+        RoundedPolygon {
+            vertices: polygon.vertices.iter().map(|v| transform.world_pos(v)).collect(),
+            normals: polygon.normals.iter().map(|n| transform.world_dir(n)).collect(),
+            radius: polygon.radius,
+        }
+    }
+    
+    pub fn from_capsule(capsule: &Capsule, transform: &Transform) -> RoundedPolygon {
+        let [a, b] = capsule.end_points();
+        
+        RoundedPolygon::from_segment_points(transform.world_pos(&a), transform.world_pos(&b),
+                                            transform.world_dir(&Vec2::DOWN), capsule.radius)
+    }
+    
+    pub fn from_segment(segment: &Segment, transform: &Transform) -> RoundedPolygon {
+        RoundedPolygon::from_segment_points(transform.world_pos(&segment.a), transform.world_pos(&segment.b),
+                                            transform.world_dir(&segment.normal()), 0.0)
+    }
+    
+    /// Creates a rounded polygon from the world space end points `a` and `b` of a segment, where
+    /// `normal` is the normal of the face from `a` to `b`.
+    pub fn from_segment_points(a: Vec2, b: Vec2, normal: Vec2, radius: f32) -> RoundedPolygon {
+        RoundedPolygon {
+            vertices: vec![a, b],
+            normals: vec![normal, -normal],
+            radius,
+        }
+    }
+    
+    /// Returns the average of the vertices, which lies inside the polygon.
+    pub fn center(&self) -> Vec2 {
+        self.vertices.iter().fold(Vec2::ZERO, |sum, v| sum + *v) / self.vert_count() as f32
+    }
+    
+    #[inline]
+    fn vert_count(&self) -> usize {
+        self.vertices.len()
+    }
+    
+    fn face(&self, index: usize) -> Face {
+        Face::new(self.vertices[index], self.vertices[(index + 1) % self.vert_count()], self.normals[index])
+    }
+    
+    /// Returns the face of this polygon along which the core of the other polygon is separated
+    /// the most, and the separation itself. The separation is negative if the cores overlap.
+    fn max_separation_face(&self, other: &RoundedPolygon) -> (usize, f32) {
+        let mut face_index = 0usize;
+        let mut max_sep = -f32::INFINITY;
+        
+        for i in 0..self.vert_count() {
+            let face = self.face(i);
+            
+            let separation = other.vertices.iter()
+                                  .map(|v| face.distance(v))
+                                  .fold(f32::INFINITY, f32::min);
+            
+            if separation > max_sep {
+                max_sep = separation;
+                face_index = i;
+            }
+        }
+        
+        (face_index, max_sep)
+    }
+    
+    /// Returns the index of the face most anti-parallel to `ref_face_normal`.
+    fn incident_face(&self, ref_face_normal: &Vec2) -> usize {
+        let mut min_dot = f32::INFINITY;
+        let mut inc_face_idx = 0usize;
+        
+        for i in 0..self.vert_count() {
+            let dot = self.normals[i].dot(ref_face_normal);
+            
+            if dot < min_dot {
+                min_dot = dot;
+                inc_face_idx = i;
+            }
+        }
+        
+        inc_face_idx
+    }
+}
+
+/// Collides a segment from `a` to `b` in world space, inflated by `radius`, with a circle. The
+/// contact normal points from the segment to the circle.
+///
+/// If the circle's center lies on the segment, the contact normal is `side_normal`.
+pub(super) fn collide_rounded_segment_circle(a: Vec2, b: Vec2, radius: f32, side_normal: Vec2,
+                                             circle: &Circle, circle_center: Vec2) -> Option<Vec<Contact>> {
+    // Closest point to the circle on the segment
+    let segment = b - a;
+    let t = clamp01((circle_center - a).dot(&segment) / segment.sqr_len().max(f32::EPSILON));
+    let closest = a + segment * t;
+    
+    let r = radius + circle.radius;
+    let normal = circle_center - closest;
+    
+    if normal.sqr_len() > r * r {
+        return None;
+    }
+    
+    let distance = normal.len();
+    let normal = if distance > f32::EPSILON { normal / distance } else { side_normal };
+    
+    let contact_position = closest + normal * radius;
+    
+    let contact = Contact::new(contact_position, r - distance, normal);
+    
+    Some(vec![contact])
+}
+
+/// Returns the parameters along segments `a1`-`a2` and `b1`-`b2` of the closest points between them.
+pub(super) fn closest_segment_params(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> (f32, f32) {
+    let da = a2 - a1;
+    let db = b2 - b1;
+    let r = a1 - b1;
+    
+    let sqr_len_a = da.sqr_len();
+    let sqr_len_b = db.sqr_len();
+    let f = db.dot(&r);
+    
+    if sqr_len_a <= f32::EPSILON && sqr_len_b <= f32::EPSILON {
+        return (0.0, 0.0);
+    }
+    
+    if sqr_len_a <= f32::EPSILON {
+        return (0.0, clamp01(f / sqr_len_b));
+    }
+    
+    let c = da.dot(&r);
+    
+    if sqr_len_b <= f32::EPSILON {
+        return (clamp01(-c / sqr_len_a), 0.0);
+    }
+    
+    let b = da.dot(&db);
+    let denom = sqr_len_a * sqr_len_b - b * b;
+    
+    // Parallel segments have no unique closest points; the middle of the part of a overlapping b
+    // is only at a vertex if the segments meet end to end
+    let s = if denom > f32::EPSILON {
+        clamp01((b * f - c * sqr_len_b) / denom)
+    } else {
+        0.5 * (clamp01(-c / sqr_len_a) + clamp01((b - c) / sqr_len_a))
+    };
+    let t = (b * s + f) / sqr_len_b;
+    
+    if t < 0.0 {
+        (clamp01(-c / sqr_len_a), 0.0)
+    } else if t > 1.0 {
+        (clamp01((b - c) / sqr_len_a), 1.0)
+    } else {
+        (s, t)
+    }
+}
+
+/// Collides two rounded polygons. The contact normals point from `a` to `b`.
+pub(super) fn collide_rounded_polygons(a: &RoundedPolygon, b: &RoundedPolygon) -> Option<Vec<Contact>> {
+    let (a_face_idx, a_sep) = a.max_separation_face(b);
+    let (b_face_idx, b_sep) = b.max_separation_face(a);
+    
+    let radius = a.radius + b.radius;
+    
+    if a_sep > radius || b_sep > radius {
+        return None;
+    }
+    
+    let a_is_ref_poly = b_sep <= a_sep + 0.1 * CORE_SEPARATION_TOLERANCE;
+    
+    let (a_face_idx, b_face_idx) = if a_is_ref_poly {
+        (a_face_idx, b.incident_face(&a.normals[a_face_idx]))
+    } else {
+        (a.incident_face(&b.normals[b_face_idx]), b_face_idx)
+    };
+    
+    if a_sep.max(b_sep) > CORE_SEPARATION_TOLERANCE {
+        // The cores are separated; if their closest features are vertices, the rounded corners
+        // touch and the contact normal joins the vertices
+        let a_face = a.face(a_face_idx);
+        let b_face = b.face(b_face_idx);
+        
+        let (s, t) = closest_segment_params(a_face.a, a_face.b, b_face.a, b_face.b);
+        
+        let is_vertex = |param: f32| param == 0.0 || param == 1.0;
+        
+        if is_vertex(s) && is_vertex(t) {
+            let a_point = a_face.a + (a_face.b - a_face.a) * s;
+            let b_point = b_face.a + (b_face.b - b_face.a) * t;
+            
+            let normal = b_point - a_point;
+            let distance = normal.len();
+            
+            if distance > radius {
+                return None;
+            }
+            
+            let normal = normal / distance;
+            
+            // Contact at the midpoint of the overlapping corners
+            let a_surface = a_point + normal * a.radius;
+            let b_surface = b_point - normal * b.radius;
+            
+            return Some(vec![Contact::new((a_surface + b_surface) * 0.5, radius - distance, normal)]);
+        }
+    }
+    
+    if a_is_ref_poly {
+        clip_rounded_polygons(a, a_face_idx, b, b_face_idx, false)
+    } else {
+        clip_rounded_polygons(b, b_face_idx, a, a_face_idx, true)
+    }
+}
+
+/// Collides two rounded polygons using the face of `a` at `a_face_idx` as the reference face. The
+/// contact normals point from `a` to `b`.
+pub(super) fn collide_rounded_polygons_with_reference(a: &RoundedPolygon, a_face_idx: usize,
+                                                      b: &RoundedPolygon) -> Option<Vec<Contact>> {
+    let b_face_idx = b.incident_face(&a.normals[a_face_idx]);
+    
+    clip_rounded_polygons(a, a_face_idx, b, b_face_idx, false)
+}
+
+/// Clips the incident face of `inc_poly` against the side planes of the reference face of
+/// `ref_poly`, creating a contact for each clipped point within the combined radius.
+fn clip_rounded_polygons(ref_poly: &RoundedPolygon, ref_face_idx: usize,
+                         inc_poly: &RoundedPolygon, inc_face_idx: usize, flip: bool) -> Option<Vec<Contact>> {
+    let ref_face = ref_poly.face(ref_face_idx);
+    let inc_face = inc_poly.face(inc_face_idx);
+    
+    let tangent = ref_face.normal.cross(-1.0);
+    
+    let side_faces = [Face::new(ref_face.a, ref_face.a, -tangent),
+        Face::new(ref_face.b, ref_face.b, tangent)];
+    
+    let mut inc_points = ClipPoint::incident_points(&inc_face, ref_face_idx, inc_face_idx, inc_poly.vert_count());
+    
+    // The side planes pass through the vertices of the ref face
+    let ref_vertices_idx = [ref_face_idx, (ref_face_idx + 1) % ref_poly.vert_count()];
+    
+    for (side_face, &ref_vertex_idx) in side_faces.iter().zip(ref_vertices_idx.iter()) {
+        let (clipped, clip_count) =
+            side_face.clip_points_below(&inc_points, ClipPoint::side_feature(ref_vertex_idx, inc_face_idx));
+        
+        if clip_count < 2 {
+            return None;
+        }
+        
+        inc_points = clipped;
+    }
+    
+    let radius = ref_poly.radius + inc_poly.radius;
+    let normal = if flip { -ref_face.normal } else { ref_face.normal };
+    
+    let contacts: Vec<Contact> = inc_points.iter().filter_map(|inc_point| {
+        let d = ref_face.distance(&inc_point.position);
+        
+        if d > radius {
+            None
+        } else {
+            // Contact halfway between the surfaces
+            let position = inc_point.position + ref_face.normal * (0.5 * (ref_poly.radius - inc_poly.radius - d));
+            let feature = if flip { inc_point.feature.flipped() } else { inc_point.feature };
+            Some(Contact::new(position, radius - d, normal).with_feature(feature))
+        }
+    }).collect();
+    
+    if contacts.is_empty() {
+        None
+    } else {
+        Some(contacts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Collide;
+    use crate::collision::ContactFeature;
+    
+    fn features(contacts: &[Contact]) -> Vec<ContactFeature> {
+        contacts.iter().map(|contact| contact.feature.unwrap()).collect()
+    }
+    
+    #[test]
+    fn rounded_contact_features() {
+        let ground = Polygon::rect(10.0, 1.0);
+        let ground_transform = Transform::new(Vec2::ZERO, 0.0);
+        
+        let capsule = Capsule::new(0.5, 0.25);
+        let rounded_square = Polygon::new_rounded(Polygon::rect(1.0, 0.5).vertices, 0.125);
+        
+        let collide_capsule = |position: Vec2| {
+            ground.collide(&ground_transform, &capsule, &Transform::new(position, 0.0)).unwrap()
+        };
+        let collide_square = |position: Vec2| {
+            ground.collide(&ground_transform, &rounded_square, &Transform::new(position, 0.0)).unwrap()
+        };
+        
+        for collide in [&collide_capsule as &dyn Fn(Vec2) -> Vec<Contact>, &collide_square].iter() {
+            let contacts = collide(Vec2::new(0.3, 0.74));
+            assert_eq!(contacts.len(), 2);
+            
+            // Sliding along the ground and sinking deeper keeps the features of both points
+            assert_eq!(features(&collide(Vec2::new(2.3, 0.74))), features(&contacts));
+            assert_eq!(features(&collide(Vec2::new(-1.7, 0.7))), features(&contacts));
+        }
+        
+        // Swapping the shapes swaps the features
+        let contacts = collide_capsule(Vec2::new(0.3, 0.74));
+        let swapped = capsule.collide(&Transform::new(Vec2::new(0.3, 0.74), 0.0), &ground, &ground_transform).unwrap();
+        
+        let mut expected = features(&contacts).into_iter().map(ContactFeature::flipped).collect::<Vec<_>>();
+        let mut swapped = features(&swapped);
+        expected.sort_by_key(|feature| (feature.index_a, feature.index_b));
+        swapped.sort_by_key(|feature| (feature.index_a, feature.index_b));
+        assert_eq!(swapped, expected);
+    }
+}
@@ -0,0 +1,19 @@
+use super::{Collide, flip_contacts};
+use super::rounded_polygon::collide_rounded_segment_circle;
+use crate::shapes::{Circle, Segment};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Circle> for Segment {
+    fn collide(&self, self_transform: &Transform, other: &Circle, other_transform: &Transform) -> Option<Vec<Contact>> {
+        
+        collide_rounded_segment_circle(self_transform.world_pos(&self.a), self_transform.world_pos(&self.b), 0.0,
+                                       -self_transform.world_dir(&self.normal()), other, other_transform.position)
+    }
+}
+
+impl Collide<Segment> for Circle {
+    fn collide(&self, self_transform: &Transform, other: &Segment, other_transform: &Transform) -> Option<Vec<Contact>> {
+        flip_contacts(other.collide(other_transform, self, self_transform))
+    }
+}
@@ -0,0 +1,23 @@
+use super::Collide;
+use super::rounded_polygon::{RoundedPolygon, collide_rounded_polygons};
+use crate::shapes::{Segment, Polygon};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+impl Collide<Polygon> for Segment {
+    fn collide(&self, self_transform: &Transform, other: &Polygon, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let self_poly = RoundedPolygon::from_segment(self, self_transform);
+        let other_poly = RoundedPolygon::from_polygon(other, other_transform);
+        
+        collide_rounded_polygons(&self_poly, &other_poly)
+    }
+}
+
+impl Collide<Segment> for Polygon {
+    fn collide(&self, self_transform: &Transform, other: &Segment, other_transform: &Transform) -> Option<Vec<Contact>> {
+        let self_poly = RoundedPolygon::from_polygon(self, self_transform);
+        let other_poly = RoundedPolygon::from_segment(other, other_transform);
+        
+        collide_rounded_polygons(&self_poly, &other_poly)
+    }
+}
@@ -0,0 +1,11 @@
+use super::Collide;
+use crate::shapes::{Segment};
+use crate::world::Transform;
+use crate::collision::{Contact};
+
+/// Segments have no mass, so two segments never need to be collided.
+impl Collide for Segment {
+    fn collide(&self, _self_transform: &Transform, _other: &Segment, _other_transform: &Transform) -> Option<Vec<Contact>> {
+        None
+    }
+}
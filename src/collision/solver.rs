@@ -1,13 +1,22 @@
 use crate::collision::Contact;
 use crate::constraint::Constraint;
-use crate::world::Body;
-use crate::math::{clamp, Cross};
+use crate::world::{Body, Fixture, Material, MaterialPairs};
+use crate::math::{clamp, Cross, Mat2, Vec2};
 
 const PENETRATION_SLOP: f32 = 0.005;
 const BAUMGARTE: f32 = 0.1;
 
 const RESTITUTION_VELOCITY_SLOP: f32 = 0.5;
 
+/// How far the normals of two contacts may deviate, as `1 - cos(angle)`, for the contacts to share
+/// a manifold.
+const MANIFOLD_NORMAL_TOLERANCE: f32 = 0.001;
+
+/// The largest condition number of the normal mass matrix of a two point manifold for which the
+/// points are solved together.
+const MAX_BLOCK_CONDITION_NUMBER: f32 = 1000.0;
+
+#[derive(Clone)]
 pub struct ContactConstraint {
     pub(crate) contact: Contact,
     
@@ -19,6 +28,9 @@ pub struct ContactConstraint {
     
     restitution: f32,
     friction_coefficient: f32,
+    /// The relative velocity of the bodies along the tangent that friction drives towards, from
+    /// the surface velocities of their materials.
+    tangent_speed: f32,
 }
 
 impl ContactConstraint {
@@ -32,24 +44,33 @@ impl ContactConstraint {
             tangent_mass: 0.0,
             restitution: 0.0,
             friction_coefficient: 0.0,
+            tangent_speed: 0.0,
         }
     }
     
-    pub fn with_contacts(new_contacts: &Vec<Contact>) -> Vec<ContactConstraint> {
+    pub fn with_contacts(new_contacts: &[Contact]) -> Vec<ContactConstraint> {
         new_contacts.iter().map(|contact| ContactConstraint::new(*contact)).collect()
     }
     
-    pub fn with_persistent_contacts(old_constraints: &Vec<ContactConstraint>,
-                                    new_contacts: &Vec<Contact>) -> Vec<ContactConstraint> {
+    pub fn with_persistent_contacts(old_constraints: &[ContactConstraint],
+                                    new_contacts: &[Contact]) -> Vec<ContactConstraint> {
         let mut new_constraints: Vec<ContactConstraint> =
             new_contacts.iter().map(|contact| ContactConstraint::new(*contact)).collect();
         
         for old_constraint in old_constraints.iter() {
             const PERSISTENT_DISTANCE: f32 = 0.01;
-        
-            // Persist constraints based on proximity
+            
+            let old_contact = &old_constraint.contact;
+            
+            // Persist constraints between the same fixtures created from the same features, or based
+            // on proximity if the contacts have no features
             if let Some(near_constraint) = new_constraints.iter_mut().find(|c| {
-                (c.contact.position - old_constraint.contact.position).sqr_len() <= PERSISTENT_DISTANCE
+                let same_feature = match (c.contact.feature, old_contact.feature) {
+                    (Some(feature), Some(old_feature)) => feature == old_feature,
+                    _ => (c.contact.position - old_contact.position).sqr_len() <= PERSISTENT_DISTANCE,
+                };
+                
+                c.contact.fixture_a == old_contact.fixture_a && c.contact.fixture_b == old_contact.fixture_b && same_feature
             }) {
                 // Persist constraint data
                 near_constraint.normal_impulse = old_constraint.normal_impulse;
@@ -59,13 +80,71 @@ impl ContactConstraint {
         
         new_constraints
     }
+    
+    /// Solves the friction of the contact.
+    fn solve_tangent_velocity(&mut self, a: &mut Body, b: &mut Body) {
+        let contact = &self.contact;
+        let r_a = contact.position - a.world_center();
+        let r_b = contact.position - b.world_center();
+        
+        let rel_vel = b.velocity - a.velocity + b.angular_vel.cross(&r_b) - a.angular_vel.cross(&r_a);
+        
+        let rel_vel_tangent = contact.tangent.dot(&rel_vel);
+        
+        let j_t = -(rel_vel_tangent - self.tangent_speed) * self.tangent_mass;
+        
+        let max_friction = self.friction_coefficient * self.normal_impulse;
+        
+        let old_impulse = self.tangent_impulse;
+        self.tangent_impulse = clamp(old_impulse + j_t, -max_friction, max_friction);
+        
+        let j_t = self.tangent_impulse - old_impulse;
+        
+        a.add_impulse_at_pos(-contact.tangent * j_t, r_a);
+        b.add_impulse_at_pos(contact.tangent * j_t, r_b);
+    }
+    
+    /// Returns the relative velocity of the bodies along the normal at the contact, and the velocity
+    /// the normal impulse aims for.
+    fn normal_velocity(&self, a: &Body, b: &Body) -> (f32, f32) {
+        let contact = &self.contact;
+        let r_a = contact.position - a.world_center();
+        let r_b = contact.position - b.world_center();
+        
+        let rel_vel = b.velocity - a.velocity + b.angular_vel.cross(&r_b) - a.angular_vel.cross(&r_a);
+        
+        let rel_vel_normal = contact.normal.dot(&rel_vel);
+        
+        let res_bias = -self.restitution * f32::max(0.0, rel_vel_normal - RESTITUTION_VELOCITY_SLOP);
+        
+        (rel_vel_normal, res_bias)
+    }
+    
+    /// Solves the non-penetration of the contact on its own.
+    fn solve_normal_velocity(&mut self, a: &mut Body, b: &mut Body) {
+        let contact = &self.contact;
+        let r_a = contact.position - a.world_center();
+        let r_b = contact.position - b.world_center();
+        
+        let (rel_vel_normal, bias) = self.normal_velocity(a, b);
+        
+        let j = (-rel_vel_normal + bias) * self.normal_mass;
+        
+        let old_impulse = self.normal_impulse;
+        self.normal_impulse = f32::max(0.0, old_impulse + j);
+        
+        let j = self.normal_impulse - old_impulse;
+        
+        a.add_impulse_at_pos(-contact.normal * j, r_a);
+        b.add_impulse_at_pos(contact.normal * j, r_b);
+    }
 }
 
 impl Constraint for ContactConstraint {
     fn initialize_velocity(&mut self, a: &Body, b: &Body, _dt: f32) {
         let contact = &self.contact;
-        let r_a = contact.position - a.transform.position;
-        let r_b = contact.position - b.transform.position;
+        let r_a = contact.position - a.world_center();
+        let r_b = contact.position - b.world_center();
         
         let rel_vel = b.velocity - a.velocity + b.angular_vel.cross(&r_b) - a.angular_vel.cross(&r_a);
         let _rel_vel_normal = contact.normal.dot(&rel_vel);
@@ -86,18 +165,12 @@ impl Constraint for ContactConstraint {
         
         self.normal_mass = 1.0 / inv_normal_impulse_factor;
         self.tangent_mass = 1.0 / inv_tangent_impulse_factor;
-        
-        // Arithmetic mean
-        self.restitution = 0.5 * (a.material.restitution + b.material.restitution);
-        
-        // Geometric mean
-        self.friction_coefficient = (a.material.friction * b.material.friction).sqrt();
     }
     
     fn warm_start_velocity(&mut self, a: &mut Body, b: &mut Body, _dt: f32) {
         let contact = &self.contact;
-        let r_a = contact.position - a.transform.position;
-        let r_b = contact.position - b.transform.position;
+        let r_a = contact.position - a.world_center();
+        let r_b = contact.position - b.world_center();
         
         let impulse = self.normal_impulse * contact.normal + self.tangent_impulse * contact.tangent;
         
@@ -108,74 +181,415 @@ impl Constraint for ContactConstraint {
     fn warm_start_position(&mut self, _a: &mut Body, _b: &mut Body, _dt: f32) {}
     
     fn solve_velocity(&mut self, a: &mut Body, b: &mut Body, _dt: f32) {
+        // Solve tangent constraints first because normal constraints (non-penetration) are more important
+        self.solve_tangent_velocity(a, b);
+        self.solve_normal_velocity(a, b);
+    }
+    
+    fn solve_position(&mut self, a: &mut Body, b: &mut Body, _dt: f32) {
         let contact = &self.contact;
-        let r_a = contact.position - a.transform.position;
-        let r_b = contact.position - b.transform.position;
+        let r_a = contact.position - a.world_center();
+        let r_b = contact.position - b.world_center();
         
-        // Solve tangent constraints first because normal constraints (non-penetration) are more important
-        // Friction
-        let rel_vel = b.velocity - a.velocity + b.angular_vel.cross(&r_b) - a.angular_vel.cross(&r_a);
+        let r_a_tangent = r_a.dot(&contact.tangent);
+        let r_a_tangent_sqr = r_a_tangent * r_a_tangent;
         
-        let rel_vel_tangent = contact.tangent.dot(&rel_vel);
+        let r_b_tangent = r_b.dot(&contact.tangent);
+        let r_b_tangent_sqr = r_b_tangent * r_b_tangent;
         
-        let j_t = -rel_vel_tangent * self.tangent_mass;
+        let inv_mass_sum = a.inv_mass + b.inv_mass;
         
-        let max_friction = self.friction_coefficient * self.normal_impulse;
+        let inv_normal_impulse_factor = inv_mass_sum + r_a_tangent_sqr * a.inv_inertia + r_b_tangent_sqr * b.inv_inertia;
+        let normal_mass = 1.0 / inv_normal_impulse_factor;
         
-        let old_impulse = self.tangent_impulse;
-        self.tangent_impulse = clamp(old_impulse + j_t, -max_friction, max_friction);
+        let correction = f32::max(0.0, BAUMGARTE * (contact.penetration - PENETRATION_SLOP));
+        let pos_impulse = normal_mass * contact.normal * correction;
         
-        let j_t = self.tangent_impulse - old_impulse;
+        let center = a.world_center() - pos_impulse * a.inv_mass;
+        let rotation = a.transform.rotation() - r_a.cross(pos_impulse) * a.inv_inertia;
+        a.set_world_center(center, rotation);
         
-        a.add_impulse_at_pos(-contact.tangent * j_t, r_a);
-        b.add_impulse_at_pos(contact.tangent * j_t, r_b);
+        let center = b.world_center() + pos_impulse * b.inv_mass;
+        let rotation = b.transform.rotation() + r_b.cross(pos_impulse) * b.inv_inertia;
+        b.set_world_center(center, rotation);
+    }
+}
+
+/// The contacts between a pair of fixtures which share a normal.
+///
+/// The two points of a manifold, such as a box resting on a face, are solved together with a block
+/// solver, which finds the normal impulses of both points at once. Solving them one at a time makes
+/// each point undo part of the impulse of the other, and the box rocks back and forth between its
+/// corners. Manifolds of other sizes, and those whose points are too close together for the block
+/// to be well-conditioned, are solved one point at a time.
+///
+/// Rolling friction is solved once per manifold, as an angular impulse between the bodies bounded
+/// by the total normal impulse of the points.
+#[derive(Clone)]
+pub struct ContactManifold {
+    pub(crate) points: Vec<ContactConstraint>,
+    
+    normal: Vec2,
+    
+    rolling_impulse: f32,
+    rolling_mass: f32,
+    /// The largest rolling friction torque per unit of normal force.
+    rolling_resistance: f32,
+    
+    /// The normal mass matrix of a two point manifold and its inverse, if the points are solved
+    /// together.
+    pub(crate) block: Option<(Mat2, Mat2)>,
+}
+
+impl ContactManifold {
+    fn new(point: ContactConstraint) -> ContactManifold {
+        ContactManifold {
+            normal: point.contact.normal,
+            points: vec![point],
+            rolling_impulse: 0.0,
+            rolling_mass: 0.0,
+            rolling_resistance: 0.0,
+            block: None,
+        }
+    }
+    
+    /// Groups consecutive constraints between the same fixtures and with the same normal into
+    /// manifolds.
+    fn group(constraints: Vec<ContactConstraint>) -> Vec<ContactManifold> {
+        let mut manifolds: Vec<ContactManifold> = Vec::new();
+        
+        for constraint in constraints.into_iter() {
+            match manifolds.last_mut() {
+                Some(manifold) if manifold.shares_normal(&constraint.contact) => manifold.points.push(constraint),
+                _ => manifolds.push(ContactManifold::new(constraint)),
+            }
+        }
         
-        // Impulse
-        let rel_vel = b.velocity - a.velocity + b.angular_vel.cross(&r_b) - a.angular_vel.cross(&r_a);
+        manifolds
+    }
+    
+    fn shares_normal(&self, contact: &Contact) -> bool {
+        let first = &self.points[0].contact;
         
-        let rel_vel_normal = contact.normal.dot(&rel_vel);
+        first.fixture_a == contact.fixture_a && first.fixture_b == contact.fixture_b &&
+            1.0 - self.normal.dot(&contact.normal) <= MANIFOLD_NORMAL_TOLERANCE
+    }
+    
+    pub fn with_contacts(new_contacts: &[Contact]) -> Vec<ContactManifold> {
+        ContactManifold::group(ContactConstraint::with_contacts(new_contacts))
+    }
+    
+    pub fn with_persistent_contacts(old_manifolds: &[ContactManifold],
+                                    new_contacts: &[Contact]) -> Vec<ContactManifold> {
+        let old_constraints: Vec<ContactConstraint> =
+            old_manifolds.iter().flat_map(|manifold| manifold.points.iter().cloned()).collect();
+        
+        let mut new_manifolds =
+            ContactManifold::group(ContactConstraint::with_persistent_contacts(&old_constraints, new_contacts));
+        
+        // Persist the rolling friction of manifolds between the same fixtures
+        for new_manifold in new_manifolds.iter_mut() {
+            if let Some(old_manifold) = old_manifolds.iter().find(|old_manifold| old_manifold.fixtures() == new_manifold.fixtures()) {
+                new_manifold.rolling_impulse = old_manifold.rolling_impulse;
+            }
+        }
         
-        let res_bias = -self.restitution * f32::max(0.0, rel_vel_normal - RESTITUTION_VELOCITY_SLOP);
-        let bias = res_bias;
+        new_manifolds
+    }
+    
+    
+    /// Returns the indices of the fixtures of the first and second bodies in contact.
+    pub fn fixtures(&self) -> (usize, usize) {
+        let contact = &self.points[0].contact;
+        (contact.fixture_a, contact.fixture_b)
+    }
+    
+    /// Sets the friction, restitution and surface velocity of the points of the manifold from the
+    /// materials of the fixtures of bodies `a` and `b` in contact, or their override in
+    /// `material_pairs`.
+    pub(crate) fn set_material(&mut self, a: &Body, b: &Body, material_pairs: &MaterialPairs) {
+        let (fixture_a, fixture_b) = self.fixtures();
+        let (fixture_a, fixture_b) = (&a.fixtures()[fixture_a], &b.fixtures()[fixture_b]);
+        
+        // Anisotropic friction depends on the direction of sliding relative to each fixture
+        let tangent = self.points[0].contact.tangent;
+        let oriented = |body: &Body, fixture: &Fixture| {
+            let local_tangent = body.transform.world_transform(&fixture.transform).local_dir(&tangent);
+            
+            Material {
+                friction: fixture.material.friction_along(&local_tangent),
+                ..fixture.material.clone()
+            }
+        };
         
-        let j = (-rel_vel_normal + bias) * self.normal_mass;
+        let material = material_pairs.mix(&oriented(a, fixture_a), &oriented(b, fixture_b));
         
-        let old_impulse = self.normal_impulse;
-        self.normal_impulse = f32::max(0.0, old_impulse + j);
+        // Moving clockwise, the surface of a moves along the tangent and that of b, which faces the
+        // other way, against it, so both speeds add to the velocity of b relative to a
+        let tangent_speed = fixture_a.material.surface_velocity + fixture_b.material.surface_velocity;
         
-        let j = self.normal_impulse - old_impulse;
+        for point in self.points.iter_mut() {
+            point.friction_coefficient = material.friction;
+            point.restitution = material.restitution;
+            point.tangent_speed = tangent_speed;
+        }
+    }
+    
+    /// Returns the contacts of the manifold.
+    pub fn contacts(&self) -> impl Iterator<Item=&Contact> {
+        self.points.iter().map(|point| &point.contact)
+    }
+    
+    /// Solves the rolling friction between the bodies, which opposes their relative rotation.
+    fn solve_rolling_velocity(&mut self, a: &mut Body, b: &mut Body) {
+        let normal_impulse: f32 = self.points.iter().map(|point| point.normal_impulse).sum();
+        let max_impulse = self.rolling_resistance * normal_impulse;
         
-        a.add_impulse_at_pos(-contact.normal * j, r_a);
-        b.add_impulse_at_pos(contact.normal * j, r_b);
+        let j = -(b.angular_vel - a.angular_vel) * self.rolling_mass;
+        
+        let old_impulse = self.rolling_impulse;
+        self.rolling_impulse = clamp(old_impulse + j, -max_impulse, max_impulse);
+        
+        let j = self.rolling_impulse - old_impulse;
+        
+        a.angular_vel -= j * a.inv_inertia;
+        b.angular_vel += j * b.inv_inertia;
     }
     
-    fn solve_position(&mut self, a: &mut Body, b: &mut Body, _dt: f32) {
-        let contact = &self.contact;
-        let r_a = contact.position - a.transform.position;
-        let r_b = contact.position - b.transform.position;
+    /// Solves the normal impulses of both points of a two point manifold together, keeping the
+    /// accumulated impulses if the block has no solution.
+    fn solve_block_velocity(&mut self, k: &Mat2, normal_mass: &Mat2, a: &mut Body, b: &mut Body) {
+        let (vel_1, bias_1) = self.points[0].normal_velocity(a, b);
+        let (vel_2, bias_2) = self.points[1].normal_velocity(a, b);
         
-        let r_a_tangent = r_a.dot(&contact.tangent);
-        let r_a_tangent_sqr = r_a_tangent * r_a_tangent;
+        let old_impulse = Vec2::new(self.points[0].normal_impulse, self.points[1].normal_impulse);
         
-        let r_b_tangent = r_b.dot(&contact.tangent);
-        let r_b_tangent_sqr = r_b_tangent * r_b_tangent;
+        // The normal velocities, less their biases, that total impulses x result in are k * x + vel
+        let vel = Vec2::new(vel_1 - bias_1, vel_2 - bias_2) - k * old_impulse;
+        
+        let impulse = match solve_block(k, normal_mass, vel) {
+            Some(impulse) => impulse,
+            None => return,
+        };
+        
+        let j = impulse - old_impulse;
+        
+        for (point, j) in self.points.iter().zip([j.x, j.y].iter()) {
+            let r_a = point.contact.position - a.world_center();
+            let r_b = point.contact.position - b.world_center();
+            
+            a.add_impulse_at_pos(-self.normal * *j, r_a);
+            b.add_impulse_at_pos(self.normal * *j, r_b);
+        }
+        
+        self.points[0].normal_impulse = impulse.x;
+        self.points[1].normal_impulse = impulse.y;
+    }
+}
+
+/// Solves the linear complementarity problem of two contact points for their total normal impulses
+/// x, where `vel` is the normal velocity without any impulse, so that the velocities `k * x + vel`
+/// are non-negative, the impulses are non-negative, and each point with a positive impulse has no
+/// velocity.
+///
+/// The cases of both, either and neither point having an impulse are tried in turn. Returns `None`
+/// if none of them is a solution, which can only happen through numerical error.
+fn solve_block(k: &Mat2, normal_mass: &Mat2, vel: Vec2) -> Option<Vec2> {
+    // Both points have an impulse, and no velocity
+    let x = -(normal_mass * vel);
+    
+    if x.x >= 0.0 && x.y >= 0.0 {
+        return Some(x);
+    }
+    
+    // Only the first point has an impulse, and the second point separates
+    let x_1 = -vel.x / k.a00;
+    let vel_2 = k.a10 * x_1 + vel.y;
+    
+    if x_1 >= 0.0 && vel_2 >= 0.0 {
+        return Some(Vec2::new(x_1, 0.0));
+    }
+    
+    // Only the second point has an impulse, and the first point separates
+    let x_2 = -vel.y / k.a11;
+    let vel_1 = k.a01 * x_2 + vel.x;
+    
+    if x_2 >= 0.0 && vel_1 >= 0.0 {
+        return Some(Vec2::new(0.0, x_2));
+    }
+    
+    // Both points separate
+    if vel.x >= 0.0 && vel.y >= 0.0 {
+        return Some(Vec2::ZERO);
+    }
+    
+    None
+}
+
+impl Constraint for ContactManifold {
+    fn initialize_velocity(&mut self, a: &Body, b: &Body, dt: f32) {
+        for point in self.points.iter_mut() {
+            point.initialize_velocity(a, b, dt);
+        }
+        
+        let contact = &self.points[0].contact;
+        let fixture_a = &a.fixtures()[contact.fixture_a];
+        let fixture_b = &b.fixtures()[contact.fixture_b];
+        
+        // The rounder shape rolls, and the rougher material resists it, so a ball slows down on
+        // any ground
+        let rolling_friction = fixture_a.material.rolling_friction.max(fixture_b.material.rolling_friction);
+        let rolling_radius = fixture_a.shape.rolling_radius().max(fixture_b.shape.rolling_radius());
+        
+        self.rolling_resistance = rolling_friction * rolling_radius;
+        
+        let inv_inertia_sum = a.inv_inertia + b.inv_inertia;
+        self.rolling_mass = if inv_inertia_sum > 0.0 { 1.0 / inv_inertia_sum } else { 0.0 };
+        
+        self.block = None;
+        
+        if self.points.len() != 2 {
+            return;
+        }
+        
+        let normal = self.normal;
+        let arms = |point: &ContactConstraint| {
+            ((point.contact.position - a.world_center()).cross(normal),
+             (point.contact.position - b.world_center()).cross(normal))
+        };
+        
+        let (rn_a_1, rn_b_1) = arms(&self.points[0]);
+        let (rn_a_2, rn_b_2) = arms(&self.points[1]);
         
         let inv_mass_sum = a.inv_mass + b.inv_mass;
         
-        let inv_normal_impulse_factor = inv_mass_sum + r_a_tangent_sqr * a.inv_inertia + r_b_tangent_sqr * b.inv_inertia;
-        let normal_mass = 1.0 / inv_normal_impulse_factor;
+        let k_11 = inv_mass_sum + a.inv_inertia * rn_a_1 * rn_a_1 + b.inv_inertia * rn_b_1 * rn_b_1;
+        let k_22 = inv_mass_sum + a.inv_inertia * rn_a_2 * rn_a_2 + b.inv_inertia * rn_b_2 * rn_b_2;
+        let k_12 = inv_mass_sum + a.inv_inertia * rn_a_1 * rn_a_2 + b.inv_inertia * rn_b_1 * rn_b_2;
         
-        let correction = f32::max(0.0, BAUMGARTE * (contact.penetration - PENETRATION_SLOP));
-        let pos_impulse = normal_mass * contact.normal * correction;
+        // Points close together make the matrix nearly singular, and are solved one at a time
+        if k_11 * k_11 < MAX_BLOCK_CONDITION_NUMBER * (k_11 * k_22 - k_12 * k_12) {
+            let k = Mat2::new(k_11, k_12,
+                              k_12, k_22);
+            
+            self.block = k.inverse().map(|normal_mass| (k, normal_mass));
+        }
+    }
+    
+    fn warm_start_velocity(&mut self, a: &mut Body, b: &mut Body, dt: f32) {
+        for point in self.points.iter_mut() {
+            point.warm_start_velocity(a, b, dt);
+        }
         
-        a.transform.position -= pos_impulse * a.inv_mass;
+        a.angular_vel -= self.rolling_impulse * a.inv_inertia;
+        b.angular_vel += self.rolling_impulse * b.inv_inertia;
+    }
+    
+    fn warm_start_position(&mut self, a: &mut Body, b: &mut Body, dt: f32) {
+        for point in self.points.iter_mut() {
+            point.warm_start_position(a, b, dt);
+        }
+    }
+    
+    fn solve_velocity(&mut self, a: &mut Body, b: &mut Body, _dt: f32) {
+        if self.rolling_resistance > 0.0 {
+            self.solve_rolling_velocity(a, b);
+        }
         
-        let rotation = a.transform.rotation() - r_a.cross(pos_impulse) * a.inv_inertia;
-        a.transform.set_rotation(rotation);
+        // Solve tangent constraints first because normal constraints (non-penetration) are more important
+        for point in self.points.iter_mut() {
+            point.solve_tangent_velocity(a, b);
+        }
         
-        b.transform.position += pos_impulse * b.inv_mass;
+        match self.block {
+            Some((k, normal_mass)) => self.solve_block_velocity(&k, &normal_mass, a, b),
+            None => {
+                for point in self.points.iter_mut() {
+                    point.solve_normal_velocity(a, b);
+                }
+            }
+        }
+    }
+    
+    fn solve_position(&mut self, a: &mut Body, b: &mut Body, dt: f32) {
+        for point in self.points.iter_mut() {
+            point.solve_position(a, b, dt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::Collide;
+    use crate::shapes::{Polygon, Capsule, Circle};
+    use crate::world::Transform;
+    
+    /// Collides `shape` at `position` with a ground box, returning the constraints persisted from
+    /// `old` with the new contacts in reverse order.
+    fn persist_reversed<S>(old: &[ContactConstraint], shape: &S, position: Vec2) -> Vec<ContactConstraint>
+        where Polygon: Collide<S> {
+        let ground = Polygon::rect(10.0, 1.0);
+        let mut contacts = ground.collide(&Transform::new(Vec2::ZERO, 0.0), shape, &Transform::new(position, 0.0)).unwrap();
+        contacts.reverse();
+        
+        ContactConstraint::with_persistent_contacts(old, &contacts)
+    }
+    
+    #[test]
+    fn persistent_contacts_match_features() {
+        let ground = Polygon::rect(10.0, 1.0);
+        let square = Polygon::rect(1.0, 1.0);
+        let capsule = Capsule::new(0.5, 0.25);
         
-        let rotation = b.transform.rotation() + r_b.cross(pos_impulse) * b.inv_inertia;
-        b.transform.set_rotation(rotation);
+        let ground_transform = Transform::new(Vec2::ZERO, 0.0);
+        
+        let old_square = ground.collide(&ground_transform, &square, &Transform::new(Vec2::new(0.3, 0.95), 0.0)).unwrap();
+        let old_capsule = ground.collide(&ground_transform, &capsule, &Transform::new(Vec2::new(0.3, 0.74), 0.0)).unwrap();
+        
+        let with_impulses = |contacts: &[Contact]| {
+            let mut constraints = ContactConstraint::with_contacts(contacts);
+            
+            for (i, constraint) in constraints.iter_mut().enumerate() {
+                constraint.normal_impulse = 1.0 + i as f32;
+                constraint.tangent_impulse = -0.5 - i as f32;
+            }
+            
+            constraints
+        };
+        
+        let (old_square, old_capsule) = (with_impulses(&old_square), with_impulses(&old_capsule));
+        
+        // Slid too far to match by distance, and with the contacts in the opposite order
+        let new_square = persist_reversed(&old_square, &square, Vec2::new(2.3, 0.95));
+        let new_capsule = persist_reversed(&old_capsule, &capsule, Vec2::new(2.3, 0.74));
+        
+        for (old, new) in [(old_square, new_square), (old_capsule, new_capsule)].iter() {
+            assert_eq!(new.len(), 2);
+            
+            for constraint in new.iter() {
+                let old = old.iter().find(|old| old.contact.feature == constraint.contact.feature).unwrap();
+                
+                assert_eq!(constraint.normal_impulse, old.normal_impulse);
+                assert_eq!(constraint.tangent_impulse, old.tangent_impulse);
+            }
+            
+            assert_ne!(new[0].normal_impulse, new[1].normal_impulse);
+        }
+    }
+    
+    #[test]
+    fn persistent_contacts_without_features_match_by_distance() {
+        let ground = Polygon::rect(10.0, 1.0);
+        let circle = Circle::new(0.25);
+        
+        let contacts = ground.collide(&Transform::new(Vec2::ZERO, 0.0), &circle, &Transform::new(Vec2::new(0.3, 0.74), 0.0)).unwrap();
+        assert!(contacts[0].feature.is_none());
+        
+        let mut old = ContactConstraint::with_contacts(&contacts);
+        old[0].normal_impulse = 1.5;
+        
+        assert_eq!(persist_reversed(&old, &circle, Vec2::new(0.35, 0.74))[0].normal_impulse, 1.5);
+        assert_eq!(persist_reversed(&old, &circle, Vec2::new(0.6, 0.74))[0].normal_impulse, 0.0);
     }
 }
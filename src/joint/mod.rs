@@ -5,6 +5,7 @@ use crate::constraint::Constraint;
 use crate::world::Body;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Joint {
     Spring(SpringJoint),
 }
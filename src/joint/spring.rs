@@ -6,6 +6,7 @@ use crate::joint::Joint;
 const SPRING_DISPLACEMENT_SLOP: f32 = 0.05;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpringJoint {
     pub local_anchor_a: Vec2,
     pub local_anchor_b: Vec2,
@@ -15,17 +16,29 @@ pub struct SpringJoint {
     pub damping: f32,
     
     impulse: f32,
+    
+    // Per-step solver data, recomputed in `initialize_velocity`
+    #[cfg_attr(feature = "serde", serde(skip))]
     mass: f32,
     
+    #[cfg_attr(feature = "serde", serde(skip))]
     r_a: Vec2,
+    #[cfg_attr(feature = "serde", serde(skip))]
     r_b: Vec2,
     
+    #[cfg_attr(feature = "serde", serde(skip))]
     x: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     normal: Vec2,
+    #[cfg_attr(feature = "serde", serde(skip))]
     softness: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     k: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     beta: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     damp_coeff: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     omega: f32,
 }
 
@@ -60,12 +73,13 @@ impl SpringJoint {
 
 impl Constraint for SpringJoint {
     fn initialize_velocity(&mut self, a: &Body, b: &Body, dt: f32) {
-        self.r_a = a.transform.world_dir(&self.local_anchor_a);
-        self.r_b = b.transform.world_dir(&self.local_anchor_b);
+        // The anchors are relative to the body origins, the lever arms to the centers of mass
+        self.r_a = a.transform.world_dir(&(self.local_anchor_a - a.local_center()));
+        self.r_b = b.transform.world_dir(&(self.local_anchor_b - b.local_center()));
         
         self.omega = 2.0 * PI * self.frequency;
         
-        let displacement = b.transform.position + self.r_b - a.transform.position - self.r_a;
+        let displacement = b.world_center() + self.r_b - a.world_center() - self.r_a;
         let length = displacement.len();
         
         self.x = length - self.distance;
@@ -13,8 +13,14 @@ mod constraint;
 mod world;
 mod joint;
 
+#[cfg(feature = "rube")]
+pub mod rube;
+
 pub use crate::world::debug;
+#[cfg(feature = "scene")]
+pub use crate::world::scene;
 
 pub use crate::math::{Vec2, Mat2, Cross, Bounds};
-pub use crate::world::{World, Body, BodyId, Material, Transform};
+pub use crate::world::{World, WorldSnapshot, Body, BodyId, Material, MaterialId, MaterialPair, CombineRule, Fixture, Filter, Transform};
 pub use crate::joint::{Joint, SpringJoint};
+pub use crate::collision::{DistanceOutput, distance};
@@ -6,6 +6,7 @@ use crate::math::Vec2;
 /// a `Bounds` ensures that all operations - including expansion, intersection and overlap checks -
 /// are performed quickly and efficiently.
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bounds {
     /// The minimum corner point of the bounding volume. All points belonging to this volume are
     /// guaranteed to have components greater than the corresponding components of `min`.
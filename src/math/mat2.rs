@@ -7,6 +7,7 @@ use super::Vec2;
 ///
 /// The elements are named based on their zero-based row-column positions.
 #[derive(PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mat2 {
     /// The element of the 1st row and 1st column.
     pub a00: f32,
@@ -69,6 +70,49 @@ impl Mat2 {
                   self.a01, self.a11)
     }
     
+    /// Returns the determinant of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physics2d::Mat2;
+    ///
+    /// let a = Mat2::new(3.0, 1.0,
+    ///                   8.0, 2.0);
+    ///
+    /// assert_eq!(a.determinant(), -2.0);
+    /// ```
+    pub fn determinant(&self) -> f32 {
+        self.a00 * self.a11 - self.a01 * self.a10
+    }
+    
+    /// Returns the inverse of the matrix, or `None` if the matrix is singular.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physics2d::Mat2;
+    ///
+    /// let a = Mat2::new(3.0, 1.0,
+    ///                   8.0, 2.0);
+    /// let a_inv = a.inverse().unwrap();
+    ///
+    /// assert_eq!(a_inv * a, Mat2::I);
+    /// assert_eq!(Mat2::new(1.0, 2.0, 2.0, 4.0).inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Mat2> {
+        let det = self.determinant();
+        
+        if det == 0.0 {
+            return None;
+        }
+        
+        let inv_det = 1.0 / det;
+        
+        Some(Mat2::new(self.a11 * inv_det, -self.a01 * inv_det,
+                       -self.a10 * inv_det, self.a00 * inv_det))
+    }
+    
     pub const I: Mat2 = Mat2 {
         a00: 1.0,
         a01: 0.0,
@@ -4,7 +4,8 @@ use std::ops::{Add, Sub, Neg, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssi
 ///
 /// The `Vec2` type can be used to represent anything that has two dimensions: a size, a point, a velocity, etc.
 ///
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(PartialEq, Copy, Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2 {
     /// X coordinate of the vector.
     pub x: f32,
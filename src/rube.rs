@@ -0,0 +1,704 @@
+//! An importer for Box2D JSON scenes exported by [R.U.B.E.](https://www.iforce2d.net/rube/).
+//!
+//! Bodies, their fixtures, distance joints, gravity and solver settings are mapped onto a `World`.
+//! Content that cannot be represented is skipped or approximated, and reported as a `RubeWarning`.
+
+use std::collections::HashMap;
+use std::{error, fmt, io};
+
+use serde::Deserialize;
+
+use crate::math::Vec2;
+use crate::shapes::{Circle, Polygon, PolygonError, Segment, Chain};
+use crate::world::{World, Body, BodyId, Material, Fixture, Filter, Transform, valid_mass_properties};
+use crate::joint::SpringJoint;
+
+/// An error that prevented a R.U.B.E. scene from being imported.
+#[derive(Debug)]
+pub enum RubeError {
+    /// The scene could not be read or parsed.
+    Json(serde_json::Error),
+    /// The joint with the given index references a body index that does not exist.
+    InvalidJointBody { joint: usize },
+}
+
+impl fmt::Display for RubeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RubeError::Json(ref error) => write!(f, "invalid R.U.B.E. scene: {}", error),
+            RubeError::InvalidJointBody { joint } => write!(f, "joint {} references an invalid body", joint),
+        }
+    }
+}
+
+impl error::Error for RubeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            RubeError::Json(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for RubeError {
+    fn from(error: serde_json::Error) -> RubeError {
+        RubeError::Json(error)
+    }
+}
+
+/// Content of a R.U.B.E. scene that could not be represented exactly.
+///
+/// Bodies, fixtures and joints are identified by their index in the scene.
+#[derive(Clone, PartialEq, Debug)]
+pub enum RubeWarning {
+    /// The body has no fixture that could be imported and was skipped.
+    NoFixtures { body: usize },
+    /// The body is kinematic and was imported as a static body.
+    KinematicBody { body: usize },
+    /// The body has a negative or non-finite mass, inertia or center of mass, which was ignored.
+    InvalidMassData { body: usize },
+    /// The fixture is an open chain shape with ghost vertices at its ends, which were ignored.
+    ChainGhostVertices { body: usize, fixture: usize },
+    /// The fixture is a chain shape with too few vertices and was skipped.
+    DegenerateChain { body: usize, fixture: usize },
+    /// The fixture is a polygon or chain shape with different numbers of x and y coordinates, and
+    /// was skipped.
+    MismatchedVertices { body: usize, fixture: usize },
+    /// The fixture is a polygon shape whose vertices do not form a valid polygon, and was skipped.
+    InvalidPolygon { body: usize, fixture: usize, error: PolygonError },
+    /// The fixture is an edge shape with ghost vertices, which were ignored.
+    EdgeGhostVertices { body: usize, fixture: usize },
+    /// The fixture is a sensor and was skipped.
+    SensorFixture { body: usize, fixture: usize },
+    /// The fixture has no circle, polygon, chain or edge shape and was skipped.
+    UnsupportedShape { body: usize, fixture: usize },
+    /// The joint type is not supported and the joint was skipped.
+    UnsupportedJoint { joint: usize, joint_type: String },
+    /// The joint is a rigid distance joint (with a frequency of zero) and was skipped.
+    RigidDistanceJoint { joint: usize },
+    /// The joint is attached to a body that was skipped, and was skipped as well.
+    SkippedJointBody { joint: usize },
+}
+
+impl fmt::Display for RubeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RubeWarning::NoFixtures { body } =>
+                write!(f, "body {} has no supported fixtures and was skipped", body),
+            RubeWarning::KinematicBody { body } =>
+                write!(f, "kinematic body {} was imported as a static body", body),
+            RubeWarning::InvalidMassData { body } =>
+                write!(f, "invalid mass data of body {} was ignored", body),
+            RubeWarning::ChainGhostVertices { body, fixture } =>
+                write!(f, "end ghost vertices of chain fixture {} of body {} were ignored", fixture, body),
+            RubeWarning::DegenerateChain { body, fixture } =>
+                write!(f, "chain fixture {} of body {} has too few vertices and was skipped", fixture, body),
+            RubeWarning::MismatchedVertices { body, fixture } =>
+                write!(f, "fixture {} of body {} has mismatched vertex coordinates and was skipped", fixture, body),
+            RubeWarning::InvalidPolygon { body, fixture, error } =>
+                write!(f, "polygon fixture {} of body {} was skipped: {}", fixture, body, error),
+            RubeWarning::EdgeGhostVertices { body, fixture } =>
+                write!(f, "ghost vertices of edge fixture {} of body {} were ignored", fixture, body),
+            RubeWarning::SensorFixture { body, fixture } =>
+                write!(f, "sensor fixture {} of body {} was skipped", fixture, body),
+            RubeWarning::UnsupportedShape { body, fixture } =>
+                write!(f, "fixture {} of body {} has no supported shape and was skipped", fixture, body),
+            RubeWarning::UnsupportedJoint { joint, ref joint_type } =>
+                write!(f, "joint {} of unsupported type '{}' was skipped", joint, joint_type),
+            RubeWarning::RigidDistanceJoint { joint } =>
+                write!(f, "rigid distance joint {} was skipped", joint),
+            RubeWarning::SkippedJointBody { joint } =>
+                write!(f, "joint {} is attached to a skipped body and was skipped", joint),
+        }
+    }
+}
+
+/// The result of importing a R.U.B.E. scene.
+pub struct RubeImport {
+    /// The `BodyId` of each body in the scene, by index. `None` if the body was skipped.
+    pub bodies: Vec<Option<BodyId>>,
+    /// The `BodyId` of each named body in the scene.
+    pub names: HashMap<String, BodyId>,
+    /// Content that could not be represented exactly.
+    pub warnings: Vec<RubeWarning>,
+}
+
+/// A float, written either as a number or as the hexadecimal bit pattern of an `f32`.
+struct RubeFloat(f32);
+
+impl<'de> Deserialize<'de> for RubeFloat {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<RubeFloat, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f32),
+            Bits(String),
+        }
+        
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(value) => Ok(RubeFloat(value)),
+            Raw::Bits(bits) => u32::from_str_radix(&bits, 16)
+                .map(|bits| RubeFloat(f32::from_bits(bits)))
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+impl Default for RubeFloat {
+    fn default() -> RubeFloat {
+        RubeFloat(0.0)
+    }
+}
+
+/// A vector, written as an object or as `0` for the zero vector.
+struct RubeVec(Vec2);
+
+impl<'de> Deserialize<'de> for RubeVec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<RubeVec, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Zero(f32),
+            Vector { x: RubeFloat, y: RubeFloat },
+        }
+        
+        match Raw::deserialize(deserializer)? {
+            Raw::Zero(0.0) => Ok(RubeVec(Vec2::ZERO)),
+            Raw::Zero(value) => Err(serde::de::Error::custom(format!("invalid vector {}", value))),
+            Raw::Vector { x, y } => Ok(RubeVec(Vec2::new(x.0, y.0))),
+        }
+    }
+}
+
+impl Default for RubeVec {
+    fn default() -> RubeVec {
+        RubeVec(Vec2::ZERO)
+    }
+}
+
+#[derive(Deserialize)]
+struct RubeVertices {
+    x: Vec<RubeFloat>,
+    y: Vec<RubeFloat>,
+}
+
+impl RubeVertices {
+    /// Returns the vertices, or `None` if there are not as many x as y coordinates.
+    fn to_vec(&self) -> Option<Vec<Vec2>> {
+        if self.x.len() != self.y.len() {
+            return None;
+        }
+        
+        Some(self.x.iter().zip(self.y.iter()).map(|(x, y)| Vec2::new(x.0, y.0)).collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct RubeCircle {
+    #[serde(default)]
+    center: RubeVec,
+    radius: RubeFloat,
+}
+
+#[derive(Deserialize)]
+struct RubePolygon {
+    vertices: RubeVertices,
+}
+
+#[derive(Deserialize)]
+struct RubeChain {
+    vertices: RubeVertices,
+    #[serde(rename = "hasPrevVertex", default)]
+    has_prev_vertex: bool,
+    #[serde(rename = "hasNextVertex", default)]
+    has_next_vertex: bool,
+}
+
+#[derive(Deserialize)]
+struct RubeEdge {
+    vertex1: RubeVec,
+    vertex2: RubeVec,
+    #[serde(rename = "hasVertex0", default)]
+    has_vertex_0: bool,
+    #[serde(rename = "hasVertex3", default)]
+    has_vertex_3: bool,
+}
+
+fn default_category_bits() -> u32 {
+    1
+}
+
+fn default_mask_bits() -> u32 {
+    0xffff
+}
+
+#[derive(Deserialize)]
+struct RubeFixture {
+    #[serde(default)]
+    density: RubeFloat,
+    #[serde(default)]
+    friction: RubeFloat,
+    #[serde(default)]
+    restitution: RubeFloat,
+    #[serde(default)]
+    sensor: bool,
+    #[serde(rename = "filter-categoryBits", default = "default_category_bits")]
+    category_bits: u32,
+    #[serde(rename = "filter-maskBits", default = "default_mask_bits")]
+    mask_bits: u32,
+    #[serde(rename = "filter-groupIndex", default)]
+    group_index: i32,
+    
+    circle: Option<RubeCircle>,
+    polygon: Option<RubePolygon>,
+    chain: Option<RubeChain>,
+    edge: Option<RubeEdge>,
+}
+
+#[derive(Deserialize)]
+struct RubeBody {
+    name: Option<String>,
+    #[serde(rename = "type", default)]
+    body_type: u32,
+    #[serde(default)]
+    position: RubeVec,
+    #[serde(default)]
+    angle: RubeFloat,
+    #[serde(rename = "linearVelocity", default)]
+    linear_velocity: RubeVec,
+    #[serde(rename = "angularVelocity", default)]
+    angular_velocity: RubeFloat,
+    #[serde(rename = "linearDamping", default)]
+    linear_damping: RubeFloat,
+    #[serde(rename = "angularDamping", default)]
+    angular_damping: RubeFloat,
+    #[serde(rename = "fixedRotation", default)]
+    fixed_rotation: bool,
+    #[serde(rename = "massData-mass")]
+    mass: Option<RubeFloat>,
+    #[serde(rename = "massData-center", default)]
+    mass_center: RubeVec,
+    #[serde(rename = "massData-I", default)]
+    mass_inertia: RubeFloat,
+    #[serde(default)]
+    fixture: Vec<RubeFixture>,
+}
+
+#[derive(Deserialize)]
+struct RubeJoint {
+    #[serde(rename = "type")]
+    joint_type: String,
+    #[serde(rename = "bodyA")]
+    body_a: usize,
+    #[serde(rename = "bodyB")]
+    body_b: usize,
+    #[serde(rename = "anchorA", default)]
+    anchor_a: RubeVec,
+    #[serde(rename = "anchorB", default)]
+    anchor_b: RubeVec,
+    #[serde(default)]
+    length: RubeFloat,
+    #[serde(default)]
+    frequency: RubeFloat,
+    #[serde(rename = "dampingRatio", default)]
+    damping_ratio: RubeFloat,
+}
+
+#[derive(Deserialize)]
+struct RubeScene {
+    gravity: Option<RubeVec>,
+    #[serde(rename = "velocityIterations")]
+    velocity_iterations: Option<u32>,
+    #[serde(rename = "positionIterations")]
+    position_iterations: Option<u32>,
+    #[serde(default)]
+    body: Vec<RubeBody>,
+    #[serde(default)]
+    joint: Vec<RubeJoint>,
+}
+
+const STATIC_BODY: u32 = 0;
+const KINEMATIC_BODY: u32 = 1;
+
+/// Converts the supported fixtures of a body, reporting unsupported ones.
+fn import_fixtures(body_index: usize, body: &RubeBody, warnings: &mut Vec<RubeWarning>) -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    
+    for (fixture_index, fixture) in body.fixture.iter().enumerate() {
+        let (body, fixture_ref) = (body_index, fixture_index);
+        
+        if fixture.sensor {
+            warnings.push(RubeWarning::SensorFixture { body, fixture: fixture_ref });
+            continue;
+        }
+        
+        let (shape, position) = if let Some(ref circle) = fixture.circle {
+            (Circle::new(circle.radius.0).into_shape(), circle.center.0)
+        } else if let Some(ref polygon) = fixture.polygon {
+            let vertices = match polygon.vertices.to_vec() {
+                Some(vertices) => vertices,
+                None => {
+                    warnings.push(RubeWarning::MismatchedVertices { body, fixture: fixture_ref });
+                    continue;
+                },
+            };
+            
+            match Polygon::try_new(vertices) {
+                Ok(polygon) => (polygon.into_shape(), Vec2::ZERO),
+                Err(error) => {
+                    warnings.push(RubeWarning::InvalidPolygon { body, fixture: fixture_ref, error });
+                    continue;
+                },
+            }
+        } else if let Some(ref chain) = fixture.chain {
+            let mut vertices = match chain.vertices.to_vec() {
+                Some(vertices) => vertices,
+                None => {
+                    warnings.push(RubeWarning::MismatchedVertices { body, fixture: fixture_ref });
+                    continue;
+                },
+            };
+            
+            // Loops repeat their first vertex at the end
+            let closed = vertices.len() > 3 && vertices.first() == vertices.last();
+            
+            if closed {
+                vertices.pop();
+                (Chain::new_loop(vertices).into_shape(), Vec2::ZERO)
+            } else if vertices.len() >= 2 {
+                if chain.has_prev_vertex || chain.has_next_vertex {
+                    warnings.push(RubeWarning::ChainGhostVertices { body, fixture: fixture_ref });
+                }
+                
+                (Chain::new(vertices).into_shape(), Vec2::ZERO)
+            } else {
+                warnings.push(RubeWarning::DegenerateChain { body, fixture: fixture_ref });
+                continue;
+            }
+        } else if let Some(ref edge) = fixture.edge {
+            if edge.has_vertex_0 || edge.has_vertex_3 {
+                warnings.push(RubeWarning::EdgeGhostVertices { body, fixture: fixture_ref });
+            }
+            
+            (Segment::new(edge.vertex1.0, edge.vertex2.0).into_shape(), Vec2::ZERO)
+        } else {
+            warnings.push(RubeWarning::UnsupportedShape { body, fixture: fixture_ref });
+            continue;
+        };
+        
+        let material = Material::new(fixture.friction.0, fixture.restitution.0);
+        
+        let mut imported = Fixture::new(shape, fixture.density.0, material);
+        imported.transform = Transform::new(position, 0.0);
+        imported.filter = Filter::new(fixture.category_bits as u16, fixture.mask_bits as u16,
+                                      fixture.group_index as i16);
+        
+        fixtures.push(imported);
+    }
+    
+    fixtures
+}
+
+/// Imports the R.U.B.E. scene read from `reader` into `world`, adding its bodies and joints and
+/// applying its gravity and solver settings.
+///
+/// Distance joints with a non-zero frequency are imported as spring joints; other joint types are
+/// not supported.
+///
+/// # Examples
+///
+/// ```
+/// # use physics2d::{World, rube};
+/// let json = r#"{
+///     "gravity": { "x": 0, "y": -10 },
+///     "body": [
+///         { "name": "ball", "type": 2, "position": 0,
+///           "fixture": [{ "density": 1, "circle": { "center": 0, "radius": 0.5 } }] }
+///     ]
+/// }"#;
+///
+/// let mut world = World::default();
+/// let import = rube::import(json.as_bytes(), &mut world).unwrap();
+///
+/// assert!(import.warnings.is_empty());
+/// assert_eq!(world.get_body(import.names["ball"]).transform.position.y, 0.0);
+/// ```
+pub fn import<R: io::Read>(reader: R, world: &mut World) -> Result<RubeImport, RubeError> {
+    let scene: RubeScene = serde_json::from_reader(reader)?;
+    
+    for (joint_index, joint) in scene.joint.iter().enumerate() {
+        if joint.body_a >= scene.body.len() || joint.body_b >= scene.body.len() {
+            return Err(RubeError::InvalidJointBody { joint: joint_index });
+        }
+    }
+    
+    let mut warnings = Vec::new();
+    
+    if let Some(gravity) = scene.gravity {
+        world.gravity = gravity.0;
+    }
+    
+    if let Some(velocity_iterations) = scene.velocity_iterations {
+        world.velocity_iterations = velocity_iterations.min(u8::MAX as u32) as u8;
+    }
+    
+    if let Some(position_iterations) = scene.position_iterations {
+        world.position_iterations = position_iterations.min(u8::MAX as u32) as u8;
+    }
+    
+    let mut bodies = Vec::with_capacity(scene.body.len());
+    let mut names = HashMap::new();
+    
+    for (body_index, rube_body) in scene.body.iter().enumerate() {
+        let fixtures = import_fixtures(body_index, rube_body, &mut warnings);
+        
+        if fixtures.is_empty() {
+            warnings.push(RubeWarning::NoFixtures { body: body_index });
+            bodies.push(None);
+            continue;
+        }
+        
+        let mut body = Body::with_fixtures(fixtures);
+        
+        body.transform.position = rube_body.position.0;
+        body.transform.set_rotation(rube_body.angle.0);
+        body.velocity = rube_body.linear_velocity.0;
+        body.angular_vel = rube_body.angular_velocity.0;
+        body.linear_damping = rube_body.linear_damping.0;
+        body.angular_damping = rube_body.angular_damping.0;
+        
+        if rube_body.body_type == STATIC_BODY {
+            body.set_static();
+        } else if rube_body.body_type == KINEMATIC_BODY {
+            warnings.push(RubeWarning::KinematicBody { body: body_index });
+            body.set_static();
+        } else {
+            if let Some(RubeFloat(mass)) = rube_body.mass {
+                // Box2D gives the rotational inertia about the body origin
+                let center = rube_body.mass_center.0;
+                let inertia = rube_body.mass_inertia.0 - mass * center.sqr_len();
+                
+                if valid_mass_properties(mass, inertia, center) {
+                    body.set_mass_properties(mass, inertia, center);
+                } else {
+                    warnings.push(RubeWarning::InvalidMassData { body: body_index });
+                }
+            }
+            
+            body.set_fixed_rotation(rube_body.fixed_rotation);
+        }
+        
+        let body_id = world.add_body(body);
+        
+        if let Some(ref name) = rube_body.name {
+            names.insert(name.clone(), body_id);
+        }
+        
+        bodies.push(Some(body_id));
+    }
+    
+    for (joint_index, joint) in scene.joint.iter().enumerate() {
+        if joint.joint_type != "distance" {
+            warnings.push(RubeWarning::UnsupportedJoint {
+                joint: joint_index,
+                joint_type: joint.joint_type.clone(),
+            });
+            continue;
+        }
+        
+        if joint.frequency.0 == 0.0 {
+            warnings.push(RubeWarning::RigidDistanceJoint { joint: joint_index });
+            continue;
+        }
+        
+        let (body_a, body_b) = match (bodies[joint.body_a], bodies[joint.body_b]) {
+            (Some(body_a), Some(body_b)) => (body_a, body_b),
+            _ => {
+                warnings.push(RubeWarning::SkippedJointBody { joint: joint_index });
+                continue;
+            }
+        };
+        
+        let spring = SpringJoint::new(joint.anchor_a.0, joint.anchor_b.0, joint.length.0,
+                                      joint.frequency.0, joint.damping_ratio.0);
+        
+        world.add_joint((body_a, body_b), spring.into_joint());
+    }
+    
+    Ok(RubeImport {
+        bodies,
+        names,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::Shape;
+    
+    const SCENE: &str = r#"{
+        "gravity": { "x": 0, "y": -10 },
+        "velocityIterations": 10,
+        "positionIterations": 3,
+        "body": [
+            {
+                "name": "ground", "type": 0, "position": 0, "angle": 0,
+                "fixture": [
+                    { "friction": 0.5, "polygon": { "vertices": {
+                        "x": [-10, 10, 10, -10], "y": [-1, -1, 1, 1] } } },
+                    { "chain": { "vertices": { "x": [-10, 10], "y": [1, 1] } },
+                      "filter-categoryBits": 2, "filter-groupIndex": -1 }
+                ]
+            },
+            {
+                "name": "box", "type": 2, "position": { "x": 0, "y": 3 }, "angle": "00000000",
+                "linearDamping": 0.5, "fixedRotation": true,
+                "fixture": [
+                    { "density": 2, "polygon": { "vertices": {
+                        "x": [0, 1, 1, 0], "y": [0, 0, 1, 1] } } },
+                    { "sensor": true, "circle": { "center": 0, "radius": 1 } }
+                ]
+            },
+            { "name": "empty", "type": 1, "position": 0 },
+            {
+                "name": "edge", "type": 0, "position": { "x": 5, "y": 0 },
+                "fixture": [{ "edge": { "vertex1": 0, "vertex2": { "x": 2, "y": 1 },
+                                         "hasVertex0": true, "vertex0": { "x": -1, "y": 0 } } }]
+            },
+            {
+                "name": "loop", "type": 0, "position": 0,
+                "fixture": [{ "chain": { "vertices": { "x": [0, 1, 1, 0], "y": [0, 0, 1, 0] },
+                                          "hasPrevVertex": true, "hasNextVertex": true } }]
+            }
+        ],
+        "joint": [
+            { "type": "distance", "bodyA": 0, "bodyB": 1, "anchorA": 0, "anchorB": 0,
+              "length": 3, "frequency": 2, "dampingRatio": 0.5 },
+            { "type": "revolute", "bodyA": 0, "bodyB": 1 },
+            { "type": "distance", "bodyA": 0, "bodyB": 2, "length": 1, "frequency": 2 }
+        ]
+    }"#;
+    
+    #[test]
+    fn import_scene() {
+        let mut world = World::default();
+        let import = import(SCENE.as_bytes(), &mut world).unwrap();
+        
+        assert_eq!(world.gravity, Vec2::new(0.0, -10.0));
+        assert_eq!(world.velocity_iterations, 10);
+        assert_eq!(world.position_iterations, 3);
+        
+        assert_eq!(world.body_count(), 4);
+        assert_eq!(import.bodies[2], None);
+        
+        let ground = world.get_body(import.names["ground"]);
+        assert!(ground.is_static());
+        assert_eq!(ground.fixtures().len(), 2);
+        assert_eq!(ground.fixtures()[0].material.friction, 0.5);
+        assert_eq!(ground.fixtures()[1].filter, Filter::new(2, 0xffff, -1));
+        
+        // The box keeps its origin and authored vertices, with its center of mass offset
+        let box_body = world.get_body(import.names["box"]);
+        assert_eq!(box_body.transform.position, Vec2::new(0.0, 3.0));
+        assert_eq!(box_body.local_center(), Vec2::new(0.5, 0.5));
+        assert_eq!(box_body.world_center(), Vec2::new(0.5, 3.5));
+        assert_eq!(box_body.mass, 2.0);
+        assert_eq!(box_body.linear_damping, 0.5);
+        assert!(box_body.fixed_rotation());
+        
+        match *world.get_body(import.names["loop"]).shape() {
+            Shape::Chain(ref chain) => {
+                assert!(chain.is_closed());
+                assert_eq!(chain.vertices().len(), 3);
+            },
+            _ => panic!("expected a chain"),
+        }
+        
+        let joints = world.get_joints((import.names["ground"], import.names["box"])).unwrap();
+        assert_eq!(joints.len(), 1);
+        
+        assert_eq!(import.warnings, vec![
+            RubeWarning::SensorFixture { body: 1, fixture: 1 },
+            RubeWarning::NoFixtures { body: 2 },
+            RubeWarning::EdgeGhostVertices { body: 3, fixture: 0 },
+            RubeWarning::UnsupportedJoint { joint: 1, joint_type: "revolute".to_string() },
+            RubeWarning::SkippedJointBody { joint: 2 },
+        ]);
+    }
+    
+    #[test]
+    fn mismatched_vertices() {
+        let scene = r#"{ "body": [{
+            "type": 0, "position": 0,
+            "fixture": [
+                { "polygon": { "vertices": { "x": [0, 1, 1, 0], "y": [0, 0, 1] } } },
+                { "chain": { "vertices": { "x": [0, 1], "y": [0, 0, 1] } } },
+                { "circle": { "center": 0, "radius": 1 } }
+            ]
+        }] }"#;
+        
+        let mut world = World::default();
+        let import = import(scene.as_bytes(), &mut world).unwrap();
+        
+        assert_eq!(world.get_body(import.bodies[0].unwrap()).fixtures().len(), 1);
+        assert_eq!(import.warnings, vec![
+            RubeWarning::MismatchedVertices { body: 0, fixture: 0 },
+            RubeWarning::MismatchedVertices { body: 0, fixture: 1 },
+        ]);
+    }
+    
+    #[test]
+    fn unsupported_shape() {
+        let scene = r#"{ "body": [{
+            "type": 0, "position": 0,
+            "fixture": [
+                { "friction": 0.5 },
+                { "circle": { "center": 0, "radius": 1 } }
+            ]
+        }] }"#;
+        
+        let mut world = World::default();
+        let import = import(scene.as_bytes(), &mut world).unwrap();
+        
+        assert_eq!(world.get_body(import.bodies[0].unwrap()).fixtures().len(), 1);
+        assert_eq!(import.warnings, vec![RubeWarning::UnsupportedShape { body: 0, fixture: 0 }]);
+    }
+    
+    #[test]
+    fn mass_data() {
+        let scene = r#"{ "body": [{
+            "type": 2, "position": 0,
+            "massData-mass": 4, "massData-center": { "x": 1, "y": 0 }, "massData-I": 6,
+            "fixture": [{ "circle": { "center": 0, "radius": 1 } }]
+        }] }"#;
+        
+        let mut world = World::default();
+        let import = import(scene.as_bytes(), &mut world).unwrap();
+        
+        let body = world.get_body(import.bodies[0].unwrap());
+        assert_eq!(body.mass(), 4.0);
+        assert_eq!(body.inertia(), 2.0);
+        assert_eq!(body.local_center(), Vec2::new(1.0, 0.0));
+        assert!(import.warnings.is_empty());
+        
+        // An inertia about the center of mass below zero is ignored
+        let scene = scene.replace(r#""massData-I": 6"#, r#""massData-I": 3"#);
+        
+        let mut world = World::default();
+        let invalid = super::import(scene.as_bytes(), &mut world).unwrap();
+        
+        assert_eq!(world.get_body(invalid.bodies[0].unwrap()).local_center(), Vec2::ZERO);
+        assert_eq!(invalid.warnings, vec![RubeWarning::InvalidMassData { body: 0 }]);
+    }
+    
+    #[test]
+    fn invalid_joint_body() {
+        let scene = r#"{ "body": [], "joint": [{ "type": "distance", "bodyA": 0, "bodyB": 1 }] }"#;
+        
+        match import(scene.as_bytes(), &mut World::default()) {
+            Err(RubeError::InvalidJointBody { joint: 0 }) => {}
+            _ => panic!("expected an invalid joint body error"),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::math::{PI, Vec2};
+use crate::math::Bounds;
+use crate::world::Transform;
+
+/// A segment along the local x axis, centered on the origin, with a radius around it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule {
+    pub half_length: f32,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(half_length: f32, radius: f32) -> Capsule {
+        Capsule {
+            half_length,
+            radius,
+        }
+    }
+    
+    /// Returns the end points of the capsule's segment, relative to the capsule.
+    #[inline]
+    pub fn end_points(&self) -> [Vec2; 2] {
+        [Vec2::LEFT * self.half_length, Vec2::RIGHT * self.half_length]
+    }
+    
+    pub fn into_shape(self) -> super::Shape {
+        super::Shape::Capsule(self)
+    }
+}
+
+/// The core of a capsule is the segment between the centers of its caps.
+impl super::Support for Capsule {
+    fn support_point(&self, dir: &Vec2) -> Vec2 {
+        if dir.x >= 0.0 {
+            Vec2::new(self.half_length, 0.0)
+        } else {
+            Vec2::new(-self.half_length, 0.0)
+        }
+    }
+    
+    fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+impl super::Matter for Capsule {
+    fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
+        let (h, r) = (self.half_length, self.radius);
+        
+        let rect_mass = density * 4.0 * h * r;
+        let circle_mass = density * PI * r * r;
+        
+        let rect_inertia = rect_mass * (h * h + r * r) / 3.0;
+        
+        // The two caps form a circle, split into halves that are each moved away from the
+        // center by the half length (parallel axis theorem about the cap centroids)
+        let cap_centroid = 4.0 * r / (3.0 * PI);
+        let circle_inertia = circle_mass * (0.5 * r * r + h * h + 2.0 * h * cap_centroid);
+        
+        (rect_mass + circle_mass, rect_inertia + circle_inertia)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+    
+    fn bounds(&self, transform: Option<&Transform>) -> Bounds {
+        let [a, b] = self.end_points();
+        let (a, b) = if let Some(t) = transform { (t.world_pos(&a), t.world_pos(&b)) } else { (a, b) };
+        
+        let extents = Vec2::ONE * self.radius;
+        
+        Bounds::new(a.min(&b) - extents, a.max(&b) + extents)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::math::{Vec2, INFINITY};
+use crate::math::Bounds;
+use crate::world::Transform;
+use crate::collision::broad_phase::BoundsTree;
+
+/// A segment of a `Chain`, along with the vertices of its neighbouring segments.
+///
+/// The neighbouring (ghost) vertices are used to ignore collisions with the segment that would push
+/// bodies into its neighbours, so that bodies slide smoothly across the joints between segments.
+#[derive(Copy, Clone, Debug)]
+pub struct ChainSegment {
+    pub a: Vec2,
+    pub b: Vec2,
+    
+    /// The vertex before `a`, if any.
+    pub prev: Option<Vec2>,
+    /// The vertex after `b`, if any.
+    pub next: Option<Vec2>,
+}
+
+impl ChainSegment {
+    /// Returns this segment transformed into world space by `transform`.
+    pub fn into_world_segment(self, transform: &Transform) -> ChainSegment {
+        ChainSegment {
+            a: transform.world_pos(&self.a),
+            b: transform.world_pos(&self.b),
+            prev: self.prev.map(|v| transform.world_pos(&v)),
+            next: self.next.map(|v| transform.world_pos(&v)),
+        }
+    }
+}
+
+/// An open or closed polyline of two-sided segments.
+///
+/// Like a `Segment`, a chain has no mass; a body with a chain shape is always static. Segments are
+/// looked up through a bounds tree local to the chain, so long chains are cheap to collide with.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedChain", into = "SerializedChain"))]
+pub struct Chain {
+    vertices: Vec<Vec2>,
+    closed: bool,
+    
+    segment_tree: BoundsTree<usize>,
+}
+
+impl Chain {
+    /// Creates an open chain through `vertices`.
+    ///
+    /// # Panics
+    /// Panics if there are less than 2 vertices.
+    pub fn new(vertices: Vec<Vec2>) -> Chain {
+        assert!(vertices.len() >= Chain::min_vertices(false), "an open chain needs at least 2 vertices");
+        Chain::with_closed(vertices, false)
+    }
+    
+    /// Creates a closed chain (a loop) through `vertices`, joining the last vertex to the first.
+    ///
+    /// # Panics
+    /// Panics if there are less than 3 vertices.
+    pub fn new_loop(vertices: Vec<Vec2>) -> Chain {
+        assert!(vertices.len() >= Chain::min_vertices(true), "a closed chain needs at least 3 vertices");
+        Chain::with_closed(vertices, true)
+    }
+    
+    /// Returns the fewest vertices an open or closed chain can have.
+    pub(crate) fn min_vertices(closed: bool) -> usize {
+        if closed { 3 } else { 2 }
+    }
+    
+    fn with_closed(vertices: Vec<Vec2>, closed: bool) -> Chain {
+        let mut chain = Chain {
+            vertices,
+            closed,
+            segment_tree: BoundsTree::new(),
+        };
+        
+        for i in 0..chain.segment_count() {
+            let segment = chain.segment(i);
+            chain.segment_tree.insert_leaf(Bounds::new(segment.a.min(&segment.b), segment.a.max(&segment.b)), i);
+        }
+        
+        chain
+    }
+    
+    #[inline]
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+    
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+    
+    #[inline]
+    pub fn segment_count(&self) -> usize {
+        if self.closed { self.vertices.len() } else { self.vertices.len() - 1 }
+    }
+    
+    /// Returns the segment at `index`, relative to the chain.
+    pub fn segment(&self, index: usize) -> ChainSegment {
+        let count = self.vertices.len();
+        
+        let vertex = |i: usize| self.vertices[i % count];
+        
+        let prev = if self.closed || index > 0 { Some(vertex(index + count - 1)) } else { None };
+        let next = if self.closed || index + 2 < count { Some(vertex(index + 2)) } else { None };
+        
+        ChainSegment {
+            a: vertex(index),
+            b: vertex(index + 1),
+            prev,
+            next,
+        }
+    }
+    
+    /// Calls `f` with the index of every segment whose bounds intersect `bounds`, which are
+    /// relative to the chain.
+    pub(crate) fn for_each_segment_in<F>(&self, bounds: Bounds, f: F)
+        where F: FnMut(&usize) {
+        self.segment_tree.for_each_intersecting(bounds, f);
+    }
+    
+    pub fn into_shape(self) -> super::Shape {
+        super::Shape::Chain(self)
+    }
+}
+
+impl super::Matter for Chain {
+    fn mass_and_inertia(&self, _density: f32) -> (f32, f32) {
+        (0.0, 0.0)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+    
+    fn bounds(&self, transform: Option<&Transform>) -> Bounds {
+        let mut min = Vec2::ONE * INFINITY;
+        let mut max = -Vec2::ONE * INFINITY;
+        
+        for vertex in self.vertices.iter() {
+            let vertex = if let Some(t) = transform { t.world_pos(vertex) } else { *vertex };
+            min = min.min(&vertex);
+            max = max.max(&vertex);
+        }
+        
+        Bounds::new(min, max)
+    }
+}
+
+/// The serialized form of a `Chain`, without its segment tree.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedChain {
+    vertices: Vec<Vec2>,
+    closed: bool,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<SerializedChain> for Chain {
+    type Error = String;
+    
+    fn try_from(chain: SerializedChain) -> Result<Chain, String> {
+        let min_vertices = Chain::min_vertices(chain.closed);
+        
+        if chain.vertices.len() < min_vertices {
+            return Err(format!("a chain needs at least {} vertices", min_vertices));
+        }
+        
+        Ok(Chain::with_closed(chain.vertices, chain.closed))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Chain> for SerializedChain {
+    fn from(chain: Chain) -> SerializedChain {
+        SerializedChain {
+            vertices: chain.vertices,
+            closed: chain.closed,
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crate::math::Bounds;
 use crate::world::Transform;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Circle {
     pub radius: f32,
 }
@@ -19,6 +20,17 @@ impl Circle {
     }
 }
 
+/// The core of a circle is its center.
+impl super::Support for Circle {
+    fn support_point(&self, _dir: &Vec2) -> Vec2 {
+        Vec2::ZERO
+    }
+    
+    fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
 impl super::Matter for Circle {
     fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
         let mass = density * PI * self.radius * self.radius;
@@ -27,6 +39,10 @@ impl super::Matter for Circle {
         (mass, inertia)
     }
     
+    fn center_of_mass(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+    
     fn bounds(&self, transform: Option<&Transform>) -> Bounds {
         let center = transform.map_or(Vec2::ZERO, |t| t.position);
         let extents = Vec2::ONE * self.radius;
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use crate::math::{Vec2, Bounds, PI};
+use crate::world::Transform;
+use super::{Polygon, PolygonError, Matter, Support};
+
+/// The number of directions in which the support mapping of a `Convex` is sampled for its outline.
+const OUTLINE_SAMPLES: usize = 64;
+
+/// A convex shape described by a user-defined support mapping.
+///
+/// Convex shapes collide with every other shape using the generic GJK and EPA narrow phase, so a
+/// new kind of convex shape only needs to implement `Support`. The mass properties are computed
+/// from the outline of the shape, which is sampled from the support mapping in 64 directions.
+///
+/// Convex shapes cannot be serialized with serde; scenes save them as their outline polygon.
+///
+/// # Examples
+/// ```
+/// # use physics2d::{Vec2, shapes::{Convex, Support}, Body, Material};
+/// /// An axis aligned rectangle centered on the origin.
+/// struct Rect(Vec2);
+///
+/// impl Support for Rect {
+///     fn support_point(&self, dir: &Vec2) -> Vec2 {
+///         Vec2::new(self.0.x.copysign(dir.x), self.0.y.copysign(dir.y))
+///     }
+///
+///     fn radius(&self) -> f32 {
+///         0.0
+///     }
+/// }
+///
+/// let body = Body::new(Convex::new(Rect(Vec2::new(1.0, 0.5))).unwrap().into_shape(), 1.0, Material::default());
+/// assert!((body.mass() - 2.0).abs() < 1e-5);
+/// ```
+#[derive(Clone)]
+pub struct Convex {
+    support: Arc<dyn Support + Send + Sync>,
+    outline: Polygon,
+}
+
+impl Convex {
+    /// Creates a convex shape from the support mapping `support`.
+    ///
+    /// # Errors
+    /// Returns an error if the core of the shape has no area, or if the support mapping returns
+    /// infinite or NaN points. Use a `Circle`, `Capsule` or `Segment` for shapes whose core is a
+    /// point or a segment.
+    pub fn new<S>(support: S) -> Result<Convex, PolygonError>
+        where S: Support + Send + Sync + 'static {
+        let points: Vec<Vec2> = (0..OUTLINE_SAMPLES).map(|i| {
+            let angle = 2.0 * PI * i as f32 / OUTLINE_SAMPLES as f32;
+            support.support_point(&Vec2::new(angle.cos(), angle.sin()))
+        }).collect();
+        
+        let mut outline = Polygon::convex_hull(&points)?;
+        outline.radius = support.radius();
+        
+        Ok(Convex {
+            support: Arc::new(support),
+            outline,
+        })
+    }
+    
+    /// Returns the support mapping of the shape.
+    #[inline]
+    pub fn support(&self) -> &(dyn Support + Send + Sync) {
+        &*self.support
+    }
+    
+    /// Returns the polygon through the sampled support points of the shape, rounded by its radius.
+    #[inline]
+    pub fn outline(&self) -> &Polygon {
+        &self.outline
+    }
+    
+    pub fn into_shape(self) -> super::Shape {
+        super::Shape::Convex(self)
+    }
+}
+
+impl Support for Convex {
+    fn support_point(&self, dir: &Vec2) -> Vec2 {
+        self.support.support_point(dir)
+    }
+    
+    fn radius(&self) -> f32 {
+        self.support.radius()
+    }
+}
+
+impl Matter for Convex {
+    fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
+        self.outline.mass_and_inertia(density)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        self.outline.center_of_mass()
+    }
+    
+    fn bounds(&self, transform: Option<&Transform>) -> Bounds {
+        let identity = Transform::new(Vec2::ZERO, 0.0);
+        let transform = transform.unwrap_or(&identity);
+        
+        // The furthest points of the shape along the world axes bound it exactly
+        let extreme = |dir: Vec2| transform.world_pos(&self.support_point(&transform.local_dir(&dir)));
+        
+        let radius = Vec2::ONE * self.radius();
+        let min = Vec2::new(extreme(Vec2::LEFT).x, extreme(Vec2::DOWN).y) - radius;
+        let max = Vec2::new(extreme(Vec2::RIGHT).x, extreme(Vec2::UP).y) + radius;
+        
+        Bounds::new(min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A segment from `-self.0` to `self.0`.
+    struct Line(Vec2);
+    
+    impl Support for Line {
+        fn support_point(&self, dir: &Vec2) -> Vec2 {
+            if dir.dot(&self.0) >= 0.0 { self.0 } else { -self.0 }
+        }
+        
+        fn radius(&self) -> f32 {
+            0.0
+        }
+    }
+    
+    #[test]
+    fn degenerate_core() {
+        assert_eq!(Convex::new(Line(Vec2::ZERO)).err(), Some(PolygonError::TooFewVertices));
+        assert!(Convex::new(Line(Vec2::new(1.0, 0.5))).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use crate::math::{Vec2, Cross};
+use super::{Polygon, PolygonError};
+use super::polygon::{WELD_DISTANCE, COLLINEAR_ANGLE};
+
+/// Decomposes a simple polygon without holes, which may be concave, into convex polygons.
+///
+/// The vertices may be in either winding order; duplicate and collinear vertices are removed. The
+/// polygon is triangulated by ear clipping and the triangles are then merged into larger convex
+/// parts (Hertel-Mehlhorn), which gives at most four times the minimum number of parts.
+///
+/// The parts share the coordinates of the given vertices, so they can be used directly as the
+/// fixtures of a body.
+///
+/// # Examples
+/// ```
+/// # use physics2d::{Body, Fixture, Material, Vec2};
+/// # use physics2d::shapes;
+/// let l_shape = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0),
+///                Vec2::new(1.0, 1.0), Vec2::new(1.0, 2.0), Vec2::new(0.0, 2.0)];
+///
+/// let parts = shapes::decompose(&l_shape).unwrap();
+/// assert_eq!(parts.len(), 2);
+///
+/// let fixtures = parts.into_iter()
+///     .map(|part| Fixture::new(part.into_shape(), 1.0, Material::default()))
+///     .collect();
+///
+/// let body = Body::with_fixtures(fixtures);
+/// assert!((body.mass() - 3.0).abs() < 1e-5);
+/// ```
+pub fn decompose(vertices: &[Vec2]) -> Result<Vec<Polygon>, PolygonError> {
+    if let Some(i) = vertices.iter().position(|v| !v.x.is_finite() || !v.y.is_finite()) {
+        return Err(PolygonError::NonFiniteVertex(i));
+    }
+    
+    let mut vertices = weld(vertices);
+    
+    if vertices.len() < 3 {
+        return Err(PolygonError::TooFewVertices);
+    }
+    
+    let n = vertices.len();
+    let area: f32 = (0..n).map(|i| 0.5 * vertices[i].cross(vertices[(i + 1) % n])).sum();
+    
+    if area.abs() <= WELD_DISTANCE * WELD_DISTANCE {
+        return Err(PolygonError::ZeroArea);
+    }
+    
+    if area < 0.0 {
+        vertices.reverse();
+    }
+    
+    remove_collinear(&mut vertices);
+    
+    if !is_simple(&vertices) {
+        return Err(PolygonError::SelfIntersecting);
+    }
+    
+    let triangles = triangulate(&vertices)?;
+    
+    let parts = merge_convex(&vertices, triangles)
+        .into_iter()
+        .map(|part| Polygon::new(part.into_iter().map(|i| vertices[i]).collect()))
+        .collect();
+    
+    Ok(parts)
+}
+
+/// Returns `vertices` without those that are at the same position as the previous vertex.
+fn weld(vertices: &[Vec2]) -> Vec<Vec2> {
+    let mut welded: Vec<Vec2> = Vec::with_capacity(vertices.len());
+    
+    for &vertex in vertices {
+        match welded.last() {
+            Some(last) if (vertex - last).sqr_len() <= WELD_DISTANCE * WELD_DISTANCE => {},
+            _ => welded.push(vertex),
+        }
+    }
+    
+    // The polygon is closed, so the last vertex may also duplicate the first
+    while welded.len() > 1 && (welded[welded.len() - 1] - welded[0]).sqr_len() <= WELD_DISTANCE * WELD_DISTANCE {
+        welded.pop();
+    }
+    
+    welded
+}
+
+/// Returns the sine of the angle that the boundary turns by at `b`, positive for left turns.
+fn turn(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    (b - a).normalized().cross((c - b).normalized())
+}
+
+/// Removes vertices that lie on the line between their neighbours, including the tips of spikes
+/// that fold back onto themselves.
+fn remove_collinear(vertices: &mut Vec<Vec2>) {
+    while vertices.len() > 3 {
+        let n = vertices.len();
+        
+        let collinear = (0..n).find(|&i| {
+            turn(vertices[(i + n - 1) % n], vertices[i], vertices[(i + 1) % n]).abs() <= COLLINEAR_ANGLE
+        });
+        
+        match collinear {
+            Some(i) => { vertices.remove(i); },
+            None => break,
+        }
+    }
+}
+
+/// Whether the segments `p1`-`p2` and `q1`-`q2` cross or touch.
+fn segments_intersect(p1: Vec2, p2: Vec2, q1: Vec2, q2: Vec2) -> bool {
+    let d1 = (q2 - q1).cross(p1 - q1);
+    let d2 = (q2 - q1).cross(p2 - q1);
+    let d3 = (p2 - p1).cross(q1 - p1);
+    let d4 = (p2 - p1).cross(q2 - p1);
+    
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    
+    // Collinear end points touch the other segment if they lie within its bounds
+    let on_segment = |a: Vec2, b: Vec2, p: Vec2| p.x >= a.x.min(b.x) && p.x <= a.x.max(b.x) &&
+        p.y >= a.y.min(b.y) && p.y <= a.y.max(b.y);
+    
+    (d1 == 0.0 && on_segment(q1, q2, p1)) || (d2 == 0.0 && on_segment(q1, q2, p2)) ||
+        (d3 == 0.0 && on_segment(p1, p2, q1)) || (d4 == 0.0 && on_segment(p1, p2, q2))
+}
+
+/// Whether no two edges of the polygon cross or touch, other than neighbours at their shared vertex.
+fn is_simple(vertices: &[Vec2]) -> bool {
+    let n = vertices.len();
+    
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            
+            if segments_intersect(vertices[i], vertices[i + 1], vertices[j], vertices[(j + 1) % n]) {
+                return false;
+            }
+        }
+    }
+    
+    true
+}
+
+/// Whether `p` is inside or on the boundary of the counter-clockwise triangle `a`, `b`, `c`.
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).cross(p - a) >= 0.0 && (c - b).cross(p - b) >= 0.0 && (a - c).cross(p - c) >= 0.0
+}
+
+/// Splits a simple counter-clockwise polygon into triangles of vertex indices by ear clipping.
+fn triangulate(vertices: &[Vec2]) -> Result<Vec<Vec<usize>>, PolygonError> {
+    let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+    let mut triangles = Vec::with_capacity(vertices.len() - 2);
+    
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        
+        let is_ear = |i: usize| {
+            let (a, b, c) = (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+            let (pa, pb, pc) = (vertices[a], vertices[b], vertices[c]);
+            
+            turn(pa, pb, pc) > COLLINEAR_ANGLE && remaining.iter()
+                .filter(|&&k| k != a && k != b && k != c)
+                .all(|&k| !in_triangle(vertices[k], pa, pb, pc))
+        };
+        
+        // Every simple polygon has an ear, so this only fails for nearly degenerate input
+        let ear = (0..n).find(|&i| is_ear(i)).ok_or(PolygonError::SelfIntersecting)?;
+        
+        triangles.push(vec![remaining[(ear + n - 1) % n], remaining[ear], remaining[(ear + 1) % n]]);
+        remaining.remove(ear);
+    }
+    
+    triangles.push(remaining);
+    
+    Ok(triangles)
+}
+
+/// Merges neighbouring parts while the result is still convex.
+fn merge_convex(vertices: &[Vec2], mut parts: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    'merge: loop {
+        for i in 0..parts.len() {
+            for j in i + 1..parts.len() {
+                if let Some(merged) = try_merge(vertices, &parts[i], &parts[j]) {
+                    parts[i] = merged;
+                    parts.swap_remove(j);
+                    continue 'merge;
+                }
+            }
+        }
+        
+        return parts;
+    }
+}
+
+/// Joins two parts along a shared edge, if they have one and the result is convex.
+fn try_merge(vertices: &[Vec2], p: &[usize], q: &[usize]) -> Option<Vec<usize>> {
+    for i in 0..p.len() {
+        let (a, b) = (p[i], p[(i + 1) % p.len()]);
+        
+        // The shared edge runs from `a` to `b` in `p` and from `b` to `a` in `q`
+        let j = match q.iter().position(|&k| k == b) {
+            Some(j) if q[(j + 1) % q.len()] == a => j,
+            _ => continue,
+        };
+        
+        // `p` from `b` around to `a`, followed by `q` between `a` and `b`
+        let merged: Vec<usize> = (0..p.len()).map(|k| p[(i + 1 + k) % p.len()])
+            .chain((2..q.len()).map(|k| q[(j + k) % q.len()]))
+            .collect();
+        
+        let n = merged.len();
+        
+        let is_convex = (0..n).all(|k| {
+            turn(vertices[merged[(k + n - 1) % n]], vertices[merged[k]], vertices[merged[(k + 1) % n]]) > COLLINEAR_ANGLE
+        });
+        
+        return if is_convex { Some(merged) } else { None };
+    }
+    
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::Matter;
+    
+    fn area(polygons: &[Polygon]) -> f32 {
+        polygons.iter().map(|polygon| polygon.mass_and_inertia(1.0).0).sum()
+    }
+    
+    #[test]
+    fn decompose_comb() {
+        // A comb with three teeth pointing up, drawn clockwise with a duplicate and a collinear vertex
+        let mut comb = vec![
+            Vec2::new(0.0, 0.0), Vec2::new(0.0, 2.0), Vec2::new(1.0, 2.0), Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(3.0, 2.0), Vec2::new(3.0, 1.0),
+            Vec2::new(4.0, 1.0), Vec2::new(4.0, 2.0), Vec2::new(5.0, 2.0), Vec2::new(5.0, 0.0),
+            Vec2::new(2.5, 0.0), Vec2::new(2.5, 0.0),
+        ];
+        
+        let parts = decompose(&comb).unwrap();
+        
+        assert!((area(&parts) - 8.0).abs() < 1e-5);
+        assert!(parts.len() <= 4 * 3);
+        
+        for part in parts {
+            Polygon::try_new(part.vertices).unwrap();
+        }
+        
+        comb.reverse();
+        assert!((area(&decompose(&comb).unwrap()) - 8.0).abs() < 1e-5);
+    }
+    
+    #[test]
+    fn decompose_convex() {
+        let square = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+        
+        let parts = decompose(&square).unwrap();
+        
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].vert_count(), 4);
+    }
+    
+    #[test]
+    fn decompose_errors() {
+        let bowtie = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(2.0, 0.0), Vec2::new(0.0, 1.0)];
+        assert_eq!(decompose(&bowtie).err(), Some(PolygonError::SelfIntersecting));
+        
+        let line = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)];
+        assert_eq!(decompose(&line).err(), Some(PolygonError::ZeroArea));
+        
+        let point = [Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0)];
+        assert_eq!(decompose(&point).err(), Some(PolygonError::TooFewVertices));
+    }
+}
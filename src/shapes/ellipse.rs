@@ -0,0 +1,109 @@
+use crate::math::{PI, Vec2};
+use crate::math::Bounds;
+use crate::world::Transform;
+
+/// An ellipse centered on the origin, with its axes along the local x and y axes.
+///
+/// Ellipses collide with other shapes through the generic GJK and EPA narrow phase, and keep a
+/// single contact point on their smooth boundary, so they roll without bumping.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ellipse {
+    /// The semi-axes of the ellipse, along the local x and y axes.
+    pub half_extents: Vec2,
+}
+
+impl Ellipse {
+    pub fn new(half_extents: Vec2) -> Ellipse {
+        Ellipse {
+            half_extents,
+        }
+    }
+    
+    pub fn into_shape(self) -> super::Shape {
+        super::Shape::Ellipse(self)
+    }
+}
+
+impl super::Support for Ellipse {
+    fn support_point(&self, dir: &Vec2) -> Vec2 {
+        let (a, b) = (self.half_extents.x, self.half_extents.y);
+        
+        // The point where the normal of the ellipse, (x / a^2, y / b^2), is parallel to dir
+        let scaled = Vec2::new(a * a * dir.x, b * b * dir.y);
+        let len = (a * a * dir.x * dir.x + b * b * dir.y * dir.y).sqrt();
+        
+        if len > 0.0 { scaled / len } else { Vec2::new(a, 0.0) }
+    }
+    
+    fn radius(&self) -> f32 {
+        0.0
+    }
+}
+
+impl super::Matter for Ellipse {
+    fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
+        let (a, b) = (self.half_extents.x, self.half_extents.y);
+        
+        let mass = density * PI * a * b;
+        let inertia = 0.25 * mass * (a * a + b * b);
+        
+        (mass, inertia)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+    
+    fn bounds(&self, transform: Option<&Transform>) -> Bounds {
+        let (a, b) = (self.half_extents.x, self.half_extents.y);
+        
+        let (center, x_axis, y_axis) = match transform {
+            Some(t) => (t.position, t.world_dir(&Vec2::RIGHT), t.world_dir(&Vec2::UP)),
+            None => (Vec2::ZERO, Vec2::RIGHT, Vec2::UP),
+        };
+        
+        // The extent of the rotated ellipse along each world axis
+        let extents = Vec2::new(
+            ((a * x_axis.x).powi(2) + (b * y_axis.x).powi(2)).sqrt(),
+            ((a * x_axis.y).powi(2) + (b * y_axis.y).powi(2)).sqrt(),
+        );
+        
+        Bounds::center_extents(center, extents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Matter, Support};
+    
+    #[test]
+    fn ellipse_support_and_bounds() {
+        let ellipse = Ellipse::new(Vec2::new(2.0, 1.0));
+        
+        assert_eq!(ellipse.support_point(&Vec2::RIGHT), Vec2::new(2.0, 0.0));
+        assert_eq!(ellipse.support_point(&Vec2::DOWN), Vec2::new(0.0, -1.0));
+        
+        // The support point lies on the ellipse and its normal is along the direction
+        let dir = Vec2::new(1.0, 1.0).normalized();
+        let p = ellipse.support_point(&dir);
+        assert!((p.x * p.x / 4.0 + p.y * p.y - 1.0).abs() < 1e-5);
+        assert!(Vec2::new(p.x / 4.0, p.y).normalized().dot(&dir) > 1.0 - 1e-5);
+        
+        // Rotated by 45 degrees, both extents are sqrt((4 + 1) / 2)
+        let bounds = ellipse.bounds(Some(&Transform::new(Vec2::new(1.0, 1.0), PI / 4.0)));
+        let extent = 2.5f32.sqrt();
+        assert!((bounds.extents() - Vec2::ONE * extent).len() < 1e-5);
+        assert!((bounds.center() - Vec2::ONE).len() < 1e-5);
+        
+        // Tight bounds touch the ellipse
+        let t = Transform::new(Vec2::new(1.0, 1.0), PI / 4.0);
+        let top = t.world_pos(&ellipse.support_point(&t.local_dir(&Vec2::UP)));
+        assert!((top.y - (1.0 + extent)).abs() < 1e-5);
+        
+        let (mass, inertia) = ellipse.mass_and_inertia(1.0);
+        assert!((mass - 2.0 * PI).abs() < 1e-5);
+        assert!((inertia - 2.0 * PI * 5.0 / 4.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,249 @@
+use std::ops::Range;
+use std::{error, fmt};
+
+use crate::math::{Vec2, Bounds};
+use crate::world::Transform;
+use super::{ChainSegment, Matter};
+
+/// The reason a set of heights and a scale do not form a valid `Heightfield`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeightfieldError {
+    /// There are fewer than two heights.
+    TooFewHeights,
+    /// The height at the given index is infinite or NaN.
+    NonFiniteHeight(usize),
+    /// The distance between samples, `scale.x`, is not finite and positive.
+    InvalidSpacing,
+}
+
+impl fmt::Display for HeightfieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HeightfieldError::TooFewHeights => write!(f, "heightfield has fewer than two heights"),
+            HeightfieldError::NonFiniteHeight(i) => write!(f, "heightfield height {} is not finite", i),
+            HeightfieldError::InvalidSpacing => write!(f, "heightfield samples are not a positive distance apart"),
+        }
+    }
+}
+
+impl error::Error for HeightfieldError {}
+
+/// A regularly spaced array of heights, used for static terrain.
+///
+/// Sample `i` is at `(i * scale.x, heights[i] * scale.y)` relative to the heightfield, and each
+/// pair of neighbouring samples forms a cell whose surface is the segment between them. The surface
+/// collides like a `Chain`, so bodies slide smoothly across the joints between cells, but cells are
+/// found directly from their x coordinates, without a bounds tree.
+///
+/// Like a `Chain`, a heightfield has no mass; a body with a heightfield shape is always static.
+///
+/// # Examples
+/// ```
+/// # use physics2d::{Vec2, shapes::Heightfield};
+/// let terrain = Heightfield::new(vec![0.0, 1.0, 0.5, 2.0], Vec2::new(2.0, 0.5));
+///
+/// assert_eq!(terrain.cell_count(), 3);
+/// assert_eq!(terrain.sample(1), Vec2::new(2.0, 0.5));
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedHeightfield", into = "SerializedHeightfield"))]
+pub struct Heightfield {
+    heights: Vec<f32>,
+    scale: Vec2,
+    
+    min_height: f32,
+    max_height: f32,
+}
+
+impl Heightfield {
+    /// Creates a heightfield from `heights`, with samples `scale.x` apart and heights multiplied by
+    /// `scale.y`.
+    ///
+    /// # Panics
+    /// Panics if there are less than 2 heights, if any height is not finite, or if `scale.x` is
+    /// not finite and positive. Use `try_new` for heights from untrusted sources.
+    pub fn new(heights: Vec<f32>, scale: Vec2) -> Heightfield {
+        match Heightfield::try_new(heights, scale) {
+            Ok(heightfield) => heightfield,
+            Err(error) => panic!("{}", error),
+        }
+    }
+    
+    /// Creates a heightfield like `new`, returning an error instead of panicking for invalid heights
+    /// or spacing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{Vec2, shapes::{Heightfield, HeightfieldError}};
+    /// assert!(Heightfield::try_new(vec![0.0, 1.0], Vec2::ONE).is_ok());
+    /// assert_eq!(Heightfield::try_new(vec![0.0], Vec2::ONE).err(), Some(HeightfieldError::TooFewHeights));
+    /// assert_eq!(Heightfield::try_new(vec![0.0, 1.0], Vec2::new(0.0, 1.0)).err(),
+    ///            Some(HeightfieldError::InvalidSpacing));
+    /// ```
+    pub fn try_new(heights: Vec<f32>, scale: Vec2) -> Result<Heightfield, HeightfieldError> {
+        if heights.len() < 2 {
+            return Err(HeightfieldError::TooFewHeights);
+        }
+        
+        if let Some(i) = heights.iter().position(|h| !h.is_finite()) {
+            return Err(HeightfieldError::NonFiniteHeight(i));
+        }
+        
+        if !(scale.x.is_finite() && scale.x > 0.0) {
+            return Err(HeightfieldError::InvalidSpacing);
+        }
+        
+        let min_height = heights.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max_height = heights.iter().cloned().fold(-f32::INFINITY, f32::max);
+        
+        Ok(Heightfield {
+            heights,
+            scale,
+            min_height,
+            max_height,
+        })
+    }
+    
+    #[inline]
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+    
+    #[inline]
+    pub fn scale(&self) -> Vec2 {
+        self.scale
+    }
+    
+    #[inline]
+    pub fn cell_count(&self) -> usize {
+        self.heights.len() - 1
+    }
+    
+    /// Returns the position of sample `index`, relative to the heightfield.
+    #[inline]
+    pub fn sample(&self, index: usize) -> Vec2 {
+        Vec2::new(index as f32 * self.scale.x, self.heights[index] * self.scale.y)
+    }
+    
+    /// Returns the surface of the cell at `index`, relative to the heightfield.
+    pub fn segment(&self, index: usize) -> ChainSegment {
+        let prev = if index > 0 { Some(self.sample(index - 1)) } else { None };
+        let next = if index + 2 < self.heights.len() { Some(self.sample(index + 2)) } else { None };
+        
+        ChainSegment {
+            a: self.sample(index),
+            b: self.sample(index + 1),
+            prev,
+            next,
+        }
+    }
+    
+    /// Returns the indices of the cells whose surfaces may intersect `bounds`, which are relative
+    /// to the heightfield.
+    pub(crate) fn cells_in(&self, bounds: &Bounds) -> Range<usize> {
+        if !bounds.intersects(&self.bounds(None)) {
+            return 0..0;
+        }
+        
+        let (min_x, max_x) = (bounds.center().x - bounds.extents().x, bounds.center().x + bounds.extents().x);
+        let cell_count = self.cell_count() as f32;
+        
+        let first = (min_x / self.scale.x).floor().max(0.0).min(cell_count) as usize;
+        let last = (max_x / self.scale.x).ceil().max(0.0).min(cell_count) as usize;
+        
+        first..last
+    }
+    
+    pub fn into_shape(self) -> super::Shape {
+        super::Shape::Heightfield(self)
+    }
+}
+
+impl Matter for Heightfield {
+    fn mass_and_inertia(&self, _density: f32) -> (f32, f32) {
+        (0.0, 0.0)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+    
+    fn bounds(&self, transform: Option<&Transform>) -> Bounds {
+        let min = Vec2::new(0.0, self.min_height * self.scale.y);
+        let max = Vec2::new(self.cell_count() as f32 * self.scale.x, self.max_height * self.scale.y);
+        
+        let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+        
+        let transform = match transform {
+            Some(transform) => transform,
+            None => return Bounds::new(min.min(&max), min.max(&max)),
+        };
+        
+        // The transformed bounds of the surface are within the transformed bounds of its samples
+        let mut world_min = transform.world_pos(&corners[0]);
+        let mut world_max = world_min;
+        
+        for corner in corners.iter().skip(1) {
+            let corner = transform.world_pos(corner);
+            world_min = world_min.min(&corner);
+            world_max = world_max.max(&corner);
+        }
+        
+        Bounds::new(world_min, world_max)
+    }
+}
+
+/// The serialized form of a `Heightfield`, without its height range.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedHeightfield {
+    heights: Vec<f32>,
+    scale: Vec2,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<SerializedHeightfield> for Heightfield {
+    type Error = HeightfieldError;
+    
+    fn try_from(heightfield: SerializedHeightfield) -> Result<Heightfield, HeightfieldError> {
+        Heightfield::try_new(heightfield.heights, heightfield.scale)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Heightfield> for SerializedHeightfield {
+    fn from(heightfield: Heightfield) -> SerializedHeightfield {
+        SerializedHeightfield {
+            heights: heightfield.heights,
+            scale: heightfield.scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn heightfield_cells_in() {
+        let heightfield = Heightfield::new(vec![0.0, 1.0, 2.0, 1.0, 0.0], Vec2::new(2.0, 1.0));
+        
+        assert_eq!(heightfield.bounds(None), Bounds::new(Vec2::new(0.0, 0.0), Vec2::new(8.0, 2.0)));
+        
+        let cells = |min: Vec2, max: Vec2| heightfield.cells_in(&Bounds::new(min, max));
+        
+        assert_eq!(cells(Vec2::new(2.5, 0.5), Vec2::new(3.5, 1.5)), 1..2);
+        assert_eq!(cells(Vec2::new(3.5, 0.5), Vec2::new(4.5, 1.5)), 1..3);
+        assert_eq!(cells(Vec2::new(-5.0, 0.5), Vec2::new(20.0, 1.5)), 0..4);
+        
+        // Outside the heightfield
+        assert_eq!(cells(Vec2::new(-3.0, 0.5), Vec2::new(-1.0, 1.5)), 0..0);
+        assert_eq!(cells(Vec2::new(9.0, 0.5), Vec2::new(10.0, 1.5)), 0..0);
+        assert_eq!(cells(Vec2::new(2.5, 3.0), Vec2::new(3.5, 4.0)), 0..0);
+        
+        let segment = heightfield.segment(0);
+        assert_eq!((segment.a, segment.b), (Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0)));
+        assert_eq!((segment.prev, segment.next), (None, Some(Vec2::new(4.0, 2.0))));
+    }
+}
@@ -1,29 +1,83 @@
 mod circle;
 mod polygon;
+mod capsule;
+mod ellipse;
+mod segment;
+mod chain;
+mod heightfield;
+mod convex;
+mod decompose;
 
 pub use self::circle::Circle;
-pub use self::polygon::Polygon;
-use crate::math::Bounds;
+pub use self::polygon::{Polygon, PolygonError};
+pub use self::capsule::Capsule;
+pub use self::ellipse::Ellipse;
+pub use self::segment::Segment;
+pub use self::chain::{Chain, ChainSegment};
+pub use self::heightfield::{Heightfield, HeightfieldError};
+pub use self::convex::Convex;
+pub use self::decompose::decompose;
+use crate::math::{Bounds, Vec2};
 use crate::world::Transform;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Shape {
     Circle(Circle),
     Polygon(Polygon),
+    Capsule(Capsule),
+    Ellipse(Ellipse),
+    Segment(Segment),
+    Chain(Chain),
+    Heightfield(Heightfield),
+    /// A user-defined convex shape, which cannot be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Convex(Convex),
 }
 
-generate_match_borrow_fn_macro_for_enum!(Shape::{Circle, Polygon}; match_fn_to_shape);
+generate_match_borrow_fn_macro_for_enum!(Shape::{Circle, Polygon, Capsule, Ellipse, Segment, Chain, Heightfield, Convex}; match_fn_to_shape);
+
+impl Shape {
+    /// Returns the radius of the round part of the shape, on which it rolls. An ellipse rolls on
+    /// curves at least as round as a circle of its smaller semi-axis.
+    pub(crate) fn rolling_radius(&self) -> f32 {
+        match *self {
+            Shape::Circle(ref circle) => circle.radius,
+            Shape::Polygon(ref polygon) => polygon.radius,
+            Shape::Capsule(ref capsule) => capsule.radius,
+            Shape::Ellipse(ref ellipse) => ellipse.half_extents.x.min(ellipse.half_extents.y),
+            Shape::Convex(ref convex) => convex.radius(),
+            Shape::Segment(_) | Shape::Chain(_) | Shape::Heightfield(_) => 0.0,
+        }
+    }
+}
 
 pub trait Matter {
+    /// Returns the mass of the shape and its inertia about the shape origin.
     fn mass_and_inertia(&self, density: f32) -> (f32, f32);
+    /// Returns the center of mass of the shape in its local space.
+    fn center_of_mass(&self) -> Vec2;
     fn bounds(&self, transform: Option<&Transform>) -> Bounds;
 }
 
+/// A convex shape, described by the furthest point of its core in any direction and a radius that
+/// rounds the core.
+pub trait Support {
+    /// Returns the furthest point of the core of the shape along `dir`, both relative to the shape.
+    fn support_point(&self, dir: &Vec2) -> Vec2;
+    /// Returns the radius around the core of the shape.
+    fn radius(&self) -> f32;
+}
+
 impl Matter for Shape {
     fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
         match_fn_to_shape!(*self, mass_and_inertia(density))
     }
     
+    fn center_of_mass(&self) -> Vec2 {
+        match_fn_to_shape!(*self, center_of_mass())
+    }
+    
     fn bounds(&self, transform: Option<&Transform>) -> Bounds {
         match_fn_to_shape!(*self, bounds(transform))
     }
@@ -1,55 +1,319 @@
-use crate::math::{Vec2, Cross, INFINITY};
+use std::{error, fmt};
+
+use crate::math::{Vec2, Cross, PI, INFINITY};
 use crate::world::Transform;
 use crate::math::Bounds;
 
+/// Vertices closer than this distance are treated as the same vertex.
+pub(super) const WELD_DISTANCE: f32 = 0.0025;
+
+/// Consecutive edges whose directions differ by less than this angle (in radians) are collinear.
+pub(super) const COLLINEAR_ANGLE: f32 = 1e-4;
+
+/// The reason a set of vertices does not form a valid `Polygon`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PolygonError {
+    /// There are fewer than three distinct vertices.
+    TooFewVertices,
+    /// The vertex at the given index is infinite or NaN.
+    NonFiniteVertex(usize),
+    /// The vertex at the given index is at the same position as the previous one.
+    DuplicateVertex(usize),
+    /// All of the vertices lie on a single line.
+    ZeroArea,
+    /// The vertices are in clockwise order.
+    Clockwise,
+    /// The vertex at the given index lies on the line between its neighbours.
+    CollinearVertex(usize),
+    /// The vertices do not form a convex polygon.
+    NotConvex,
+    /// Two edges that are not neighbours cross or touch each other.
+    SelfIntersecting,
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PolygonError::TooFewVertices => write!(f, "polygon has fewer than three distinct vertices"),
+            PolygonError::NonFiniteVertex(i) => write!(f, "polygon vertex {} is not finite", i),
+            PolygonError::DuplicateVertex(i) => write!(f, "polygon vertex {} duplicates the previous vertex", i),
+            PolygonError::ZeroArea => write!(f, "polygon has no area"),
+            PolygonError::Clockwise => write!(f, "polygon vertices are in clockwise order"),
+            PolygonError::CollinearVertex(i) => write!(f, "polygon vertex {} is collinear with its neighbours", i),
+            PolygonError::NotConvex => write!(f, "polygon is not convex"),
+            PolygonError::SelfIntersecting => write!(f, "polygon edges intersect each other"),
+        }
+    }
+}
+
+impl error::Error for PolygonError {}
+
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polygon {
     pub vertices: Vec<Vec2>,
     pub normals: Vec<Vec2>,
+    /// The radius the polygon is inflated by, rounding its corners. The vertices describe the core
+    /// of the polygon, inside the skin.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub radius: f32,
 }
 
 impl Polygon {
-    pub fn new(mut vertices: Vec<Vec2>) -> Polygon {
-        let mut area = 0f32;
+    /// Creates a polygon from counter-clockwise vertices.
+    ///
+    /// The vertices are kept as given, so the polygon origin need not be its centroid. They are
+    /// not validated; use `try_new` or `convex_hull` for vertices from untrusted sources.
+    pub fn new(vertices: Vec<Vec2>) -> Polygon {
+        let mut normals: Vec<Vec2> = Vec::with_capacity(vertices.len());
         
         for i in 0..vertices.len() {
             let j: usize = (i + 1) % vertices.len();
             
-            let p1 = vertices[i];
-            let p2 = vertices[j];
+            let edge: Vec2 = vertices[j] - vertices[i];
             
-            area += 0.5 * p1.cross(p2);
+            normals.push(edge.normalized().cross(1.0));
         }
         
-        let mut centroid = Vec2::ZERO;
+        Polygon {
+            vertices,
+            normals,
+            radius: 0.0,
+        }
+    }
+    
+    /// Creates a polygon from counter-clockwise core vertices, inflated by `radius` to round its
+    /// corners.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Vec2;
+    /// # use physics2d::shapes::{Polygon, Matter};
+    /// let vertices = vec![Vec2::new(-0.9, -0.9), Vec2::new(0.9, -0.9), Vec2::new(0.9, 0.9), Vec2::new(-0.9, 0.9)];
+    /// let rounded_box = Polygon::new_rounded(vertices, 0.1);
+    ///
+    /// assert_eq!(rounded_box.bounds(None).extents(), Vec2::new(1.0, 1.0));
+    /// ```
+    pub fn new_rounded(vertices: Vec<Vec2>, radius: f32) -> Polygon {
+        Polygon {
+            radius,
+            ..Polygon::new(vertices)
+        }
+    }
+    
+    /// Creates a `width` by `height` rectangle centered on the origin.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Vec2;
+    /// # use physics2d::shapes::Polygon;
+    /// let rect = Polygon::rect(2.0, 1.0);
+    ///
+    /// assert_eq!(rect.vertices[0], Vec2::new(-1.0, -0.5));
+    /// assert_eq!(rect.vertices[2], Vec2::new(1.0, 0.5));
+    /// ```
+    pub fn rect(width: f32, height: f32) -> Polygon {
+        Polygon::rect_oriented(Vec2::new(width, height) * 0.5, Vec2::ZERO, 0.0)
+    }
+    
+    /// Creates a rectangle with the given half extents, centered on `center` and rotated by `angle`
+    /// about its center.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{Vec2, math};
+    /// # use physics2d::shapes::Polygon;
+    /// let rect = Polygon::rect_oriented(Vec2::new(2.0, 1.0), Vec2::new(3.0, 0.0), math::PI / 2.0);
+    ///
+    /// assert!((rect.vertices[0] - Vec2::new(4.0, -2.0)).len() < 1e-6);
+    /// assert!((rect.normals[0] - Vec2::RIGHT).len() < 1e-6);
+    /// ```
+    pub fn rect_oriented(half_extents: Vec2, center: Vec2, angle: f32) -> Polygon {
+        let transform = Transform::new(center, angle);
+        let (x, y) = (half_extents.x, half_extents.y);
         
-        for i in 0..vertices.len() {
-            let j: usize = (i + 1) % vertices.len();
+        let vertices = [Vec2::new(-x, -y), Vec2::new(x, -y), Vec2::new(x, y), Vec2::new(-x, y)];
+        let normals = [Vec2::DOWN, Vec2::RIGHT, Vec2::UP, Vec2::LEFT];
+        
+        Polygon {
+            vertices: vertices.iter().map(|v| transform.world_pos(v)).collect(),
+            normals: normals.iter().map(|n| transform.world_dir(n)).collect(),
+            radius: 0.0,
+        }
+    }
+    
+    /// Creates a regular polygon with `sides` vertices at `radius` from the origin, with a
+    /// horizontal bottom face.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sides` is less than 3.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Vec2;
+    /// # use physics2d::shapes::Polygon;
+    /// let hexagon = Polygon::regular(6, 1.0);
+    ///
+    /// assert_eq!(hexagon.vert_count(), 6);
+    /// assert!((hexagon.normals[0] - Vec2::DOWN).len() < 1e-6);
+    /// ```
+    pub fn regular(sides: usize, radius: f32) -> Polygon {
+        assert!(sides >= 3, "a regular polygon needs at least 3 sides");
+        
+        let step = 2.0 * PI / sides as f32;
+        
+        // The first face is centered below the origin
+        let vertices = (0..sides)
+            .map(|i| {
+                let angle = -0.5 * PI - 0.5 * step + step * i as f32;
+                Vec2::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect();
+        
+        Polygon::new(vertices)
+    }
+    
+    /// Creates a polygon from vertices that must form a convex polygon in counter-clockwise order,
+    /// with no duplicate or collinear vertices.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Vec2;
+    /// # use physics2d::shapes::{Polygon, PolygonError};
+    /// let square = vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+    /// assert!(Polygon::try_new(square.clone()).is_ok());
+    ///
+    /// let clockwise = square.into_iter().rev().collect();
+    /// assert_eq!(Polygon::try_new(clockwise).err(), Some(PolygonError::Clockwise));
+    /// ```
+    pub fn try_new(vertices: Vec<Vec2>) -> Result<Polygon, PolygonError> {
+        let n = vertices.len();
+        
+        if n < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+        
+        if let Some(i) = vertices.iter().position(|v| !v.x.is_finite() || !v.y.is_finite()) {
+            return Err(PolygonError::NonFiniteVertex(i));
+        }
+        
+        for i in 0..n {
+            let prev = vertices[(i + n - 1) % n];
+            
+            if (vertices[i] - prev).sqr_len() <= WELD_DISTANCE * WELD_DISTANCE {
+                return Err(PolygonError::DuplicateVertex(i));
+            }
+        }
+        
+        let area: f32 = (0..n).map(|i| 0.5 * vertices[i].cross(vertices[(i + 1) % n])).sum();
+        
+        if area.abs() <= WELD_DISTANCE * WELD_DISTANCE {
+            return Err(PolygonError::ZeroArea);
+        }
+        
+        if area < 0.0 {
+            return Err(PolygonError::Clockwise);
+        }
+        
+        for i in 0..n {
+            let prev = vertices[(i + n - 1) % n];
+            let next = vertices[(i + 1) % n];
             
-            let p1 = vertices[i];
-            let p2 = vertices[j];
+            let turn = (vertices[i] - prev).normalized().cross((next - vertices[i]).normalized());
             
-            centroid += (p1 + p2) * p1.cross(p2) / (6.0 * area);
+            if turn.abs() <= COLLINEAR_ANGLE {
+                return Err(PolygonError::CollinearVertex(i));
+            }
         }
         
-        for vertex in vertices.iter_mut() {
-            *vertex -= centroid;
+        // Every vertex must be on the inner side of every edge; checking the turns alone would
+        // accept self-intersecting polygons such as pentagrams
+        for i in 0..n {
+            let a = vertices[i];
+            let edge = vertices[(i + 1) % n] - a;
+            
+            if vertices.iter().any(|v| edge.cross(*v - a) < -WELD_DISTANCE * edge.len()) {
+                return Err(PolygonError::NotConvex);
+            }
         }
         
-        let mut normals: Vec<Vec2> = Vec::with_capacity(vertices.len());
+        Ok(Polygon::new(vertices))
+    }
+    
+    /// Creates the smallest convex polygon containing `points`, in any order. Points closer than
+    /// a small tolerance are merged and collinear points on the hull are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Vec2;
+    /// # use physics2d::shapes::Polygon;
+    /// let points = [Vec2::new(1.0, 1.0), Vec2::new(0.0, 0.0), Vec2::new(0.5, 0.5),
+    ///               Vec2::new(0.0, 1.0), Vec2::new(1.0, 0.0), Vec2::new(0.5, 0.0)];
+    ///
+    /// let square = Polygon::convex_hull(&points).unwrap();
+    ///
+    /// assert_eq!(square.vertices, vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0),
+    ///                                  Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)]);
+    /// ```
+    pub fn convex_hull(points: &[Vec2]) -> Result<Polygon, PolygonError> {
+        if let Some(i) = points.iter().position(|v| !v.x.is_finite() || !v.y.is_finite()) {
+            return Err(PolygonError::NonFiniteVertex(i));
+        }
         
-        for i in 0..vertices.len() {
-            let j: usize = (i + 1) % vertices.len();
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+        
+        let mut welded: Vec<Vec2> = Vec::with_capacity(points.len());
+        
+        for point in points {
+            // The points are sorted by x, so only the last few welded points can be close enough
+            let is_distinct = welded.iter().rev()
+                .take_while(|v| point.x - v.x <= WELD_DISTANCE)
+                .all(|v| (point - v).sqr_len() > WELD_DISTANCE * WELD_DISTANCE);
             
-            let edge: Vec2 = vertices[j] - vertices[i];
+            if is_distinct {
+                welded.push(point);
+            }
+        }
+        
+        if welded.len() < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+        
+        // Andrew's monotone chain: the lower hull from left to right, then the upper hull back
+        let mut hull: Vec<Vec2> = Vec::with_capacity(welded.len() + 1);
+        
+        for pass in 0..2 {
+            let start = hull.len();
             
-            normals.push(edge.normalized().cross(1.0));
+            for i in 0..welded.len() {
+                let point = if pass == 0 { welded[i] } else { welded[welded.len() - 1 - i] };
+                
+                while hull.len() >= start + 2 {
+                    let a = hull[hull.len() - 2];
+                    let b = hull[hull.len() - 1];
+                    
+                    // Pop right turns and collinear points
+                    if (b - a).cross(point - a) <= WELD_DISTANCE * (b - a).len() {
+                        hull.pop();
+                    } else {
+                        break;
+                    }
+                }
+                
+                hull.push(point);
+            }
+            
+            // The last point of each pass is the first of the next
+            hull.pop();
         }
         
-        Polygon {
-            vertices,
-            normals,
+        if hull.len() < 3 {
+            return Err(PolygonError::ZeroArea);
         }
+        
+        Ok(Polygon::new(hull))
     }
     
     #[inline]
@@ -57,16 +321,41 @@ impl Polygon {
         self.vertices.len()
     }
     
+    /// Whether the polygon is a rectangle, with four vertices and perpendicular neighbouring faces.
+    pub(crate) fn is_rect(&self) -> bool {
+        const TOLERANCE: f32 = 1e-5;
+        
+        self.vert_count() == 4 && (0..4).all(|i| self.normals[i].dot(&self.normals[(i + 1) % 4]).abs() <= TOLERANCE)
+    }
+    
     pub fn into_shape(self) -> super::Shape {
         super::Shape::Polygon(self)
     }
 }
 
 
-impl super::Matter for Polygon {
-    fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
+impl Polygon {
+    /// Returns the area, centroid and inertia about the origin per unit density.
+    ///
+    /// The skin is split into a rectangle along each edge and a circular sector at each corner,
+    /// the sectors together making up a circle of the skin radius.
+    fn area_properties(&self) -> (f32, Vec2, f32) {
+        if self.radius == 0.0 && self.is_rect() {
+            let width = (self.vertices[1] - self.vertices[0]).len();
+            let height = (self.vertices[2] - self.vertices[1]).len();
+            let center = (self.vertices[0] + self.vertices[2]) * 0.5;
+            
+            let area = width * height;
+            let inertia = area * ((width * width + height * height) / 12.0 + center.sqr_len());
+            
+            return (area, center, inertia);
+        }
+        
         let mut area = 0f32;
-        let mut density_inertia = 0f32;
+        let mut moment = Vec2::ZERO;
+        let mut inertia = 0f32;
+        
+        let r = self.radius;
         
         for i in 0..self.vert_count() {
             let j: usize = (i + 1) % self.vert_count();
@@ -74,14 +363,73 @@ impl super::Matter for Polygon {
             let p1 = self.vertices[i];
             let p2 = self.vertices[j];
             
-            let tri_area = 0.5 * p1.cross(p2).abs();
-            let tri_inertia = tri_area * (p1.sqr_len() + p2.sqr_len() + p1.dot(&p2)) / 6.0;
+            // Signed, so that the sums are correct when the origin is outside the polygon
+            let tri_area = 0.5 * p1.cross(p2);
             
             area += tri_area;
-            density_inertia += tri_inertia;
+            moment += (p1 + p2) * tri_area / 3.0;
+            inertia += tri_area * (p1.sqr_len() + p2.sqr_len() + p1.dot(&p2)) / 6.0;
+            
+            if r > 0.0 {
+                let normal = self.normals[i];
+                let length = (p2 - p1).len();
+                
+                let rect_area = length * r;
+                let rect_center = (p1 + p2) * 0.5 + normal * (0.5 * r);
+                
+                area += rect_area;
+                moment += rect_center * rect_area;
+                inertia += rect_area * ((length * length + r * r) / 12.0 + rect_center.sqr_len());
+                
+                // The sector between the normals of the previous and this edge
+                let prev_normal = self.normals[(i + self.vert_count() - 1) % self.vert_count()];
+                let angle = prev_normal.cross(normal).atan2(prev_normal.dot(&normal));
+                
+                let sector_area = 0.5 * angle * r * r;
+                let bisector = (prev_normal + normal).normalized();
+                let centroid_dist = if angle > f32::EPSILON {
+                    4.0 * r * (0.5 * angle).sin() / (3.0 * angle)
+                } else {
+                    2.0 * r / 3.0
+                };
+                let sector_center = p1 + bisector * centroid_dist;
+                
+                area += sector_area;
+                moment += sector_center * sector_area;
+                
+                // Parallel axis theorem from the vertex to the centroid and then to the origin
+                inertia += sector_area * (0.5 * r * r - centroid_dist * centroid_dist + sector_center.sqr_len());
+            }
         }
         
-        (area * density, density_inertia * density)
+        let centroid = if area != 0.0 { moment / area } else { Vec2::ZERO };
+        
+        (area, centroid, inertia)
+    }
+}
+
+impl super::Support for Polygon {
+    fn support_point(&self, dir: &Vec2) -> Vec2 {
+        *self.vertices.iter()
+            // a.dot(dir) returns the signed distance of the vertex a along dir
+            .max_by(|a, b| a.dot(dir).partial_cmp(&b.dot(dir)).unwrap())
+            .unwrap()
+    }
+    
+    fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+impl super::Matter for Polygon {
+    fn mass_and_inertia(&self, density: f32) -> (f32, f32) {
+        let (area, _, inertia) = self.area_properties();
+        
+        (area * density, inertia * density)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        self.area_properties().1
     }
     
     fn bounds(&self, transform: Option<&Transform>) -> Bounds {
@@ -94,6 +442,136 @@ impl super::Matter for Polygon {
             max = max.max(&vertex);
         }
         
-        Bounds::new(min, max)
+        let extents = Vec2::ONE * self.radius;
+        
+        Bounds::new(min - extents, max + extents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn square() -> Vec<Vec2> {
+        vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)]
+    }
+    
+    #[test]
+    fn try_new_errors() {
+        let error = |vertices: Vec<Vec2>| Polygon::try_new(vertices).err();
+        
+        assert_eq!(error(square()), None);
+        assert_eq!(error(square()[..2].to_vec()), Some(PolygonError::TooFewVertices));
+        
+        let mut vertices = square();
+        vertices[2].y = f32::NAN;
+        assert_eq!(error(vertices), Some(PolygonError::NonFiniteVertex(2)));
+        
+        let mut vertices = square();
+        vertices.insert(2, Vec2::new(1.0, 0.0));
+        assert_eq!(error(vertices), Some(PolygonError::DuplicateVertex(2)));
+        
+        let line = vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)];
+        assert_eq!(error(line), Some(PolygonError::ZeroArea));
+        
+        assert_eq!(error(square().into_iter().rev().collect()), Some(PolygonError::Clockwise));
+        
+        let mut vertices = square();
+        vertices.insert(1, Vec2::new(0.5, 0.0));
+        assert_eq!(error(vertices), Some(PolygonError::CollinearVertex(1)));
+        
+        let mut vertices = square();
+        vertices.insert(3, Vec2::new(0.5, 0.5));
+        assert_eq!(error(vertices), Some(PolygonError::NotConvex));
+        
+        let pentagram = (0..5)
+            .map(|i| {
+                let angle = i as f32 * 4.0 * crate::math::PI / 5.0;
+                Vec2::new(angle.cos(), angle.sin())
+            })
+            .collect();
+        assert_eq!(error(pentagram), Some(PolygonError::NotConvex));
+    }
+    
+    #[test]
+    fn rect_mass() {
+        use crate::shapes::Matter;
+        
+        let rect = Polygon::rect_oriented(Vec2::new(2.0, 0.5), Vec2::new(1.0, -3.0), 0.3);
+        assert!(rect.is_rect());
+        
+        // The same rectangle with an extra vertex on its bottom face takes the general path
+        let mut vertices = rect.vertices.clone();
+        vertices.insert(1, (rect.vertices[0] + rect.vertices[1]) * 0.5);
+        let general = Polygon::new(vertices);
+        assert!(!general.is_rect());
+        
+        let (mass, inertia) = rect.mass_and_inertia(3.0);
+        let (expected_mass, expected_inertia) = general.mass_and_inertia(3.0);
+        
+        assert!((mass - expected_mass).abs() < 1e-4 * expected_mass);
+        assert!((inertia - expected_inertia).abs() < 1e-4 * expected_inertia);
+        assert!((rect.center_of_mass() - Vec2::new(1.0, -3.0)).len() < 1e-5);
+        
+        let hexagon = Polygon::regular(6, 1.0);
+        let (hexagon_mass, _) = hexagon.mass_and_inertia(1.0);
+        
+        assert!(!hexagon.is_rect());
+        assert!((hexagon_mass - 1.5 * 3f32.sqrt()).abs() < 1e-5);
+    }
+    
+    #[test]
+    fn rounded_mass() {
+        use crate::shapes::Matter;
+        
+        // An off-center rounded triangle, and the same outline with finely tessellated corners
+        let core = vec![Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0), Vec2::new(1.0, 2.0)];
+        let rounded = Polygon::new_rounded(core.clone(), 0.25);
+        
+        let mut outline = Vec::new();
+        
+        for (i, vertex) in core.iter().enumerate() {
+            let prev_normal = rounded.normals[(i + 2) % 3];
+            let normal = rounded.normals[i];
+            
+            let start = prev_normal.y.atan2(prev_normal.x);
+            let arc = prev_normal.cross(normal).atan2(prev_normal.dot(&normal));
+            
+            for j in 0..=200 {
+                let angle = start + arc * j as f32 / 200.0;
+                outline.push(*vertex + Vec2::new(angle.cos(), angle.sin()) * 0.25);
+            }
+        }
+        
+        let tessellated = Polygon::new(outline);
+        
+        let (mass, inertia) = rounded.mass_and_inertia(2.0);
+        let (expected_mass, expected_inertia) = tessellated.mass_and_inertia(2.0);
+        
+        assert!((mass - expected_mass).abs() < 1e-3 * expected_mass);
+        assert!((inertia - expected_inertia).abs() < 1e-3 * expected_inertia);
+        assert!((rounded.center_of_mass() - tessellated.center_of_mass()).len() < 1e-3);
+    }
+    
+    #[test]
+    fn convex_hull() {
+        let mut points = square();
+        points.extend_from_slice(&[Vec2::new(0.0, 0.5), Vec2::new(1.0, 1.0005), Vec2::new(0.2, 0.7)]);
+        points.reverse();
+        
+        let hull = Polygon::convex_hull(&points).unwrap();
+        
+        assert_eq!(hull.vertices, square());
+        assert!(Polygon::try_new(hull.vertices).is_ok());
+        
+        let line = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(1.0, 0.0)];
+        assert_eq!(Polygon::convex_hull(&line).err(), Some(PolygonError::ZeroArea));
+        
+        let welded = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 0.001)];
+        assert_eq!(Polygon::convex_hull(&welded).err(), Some(PolygonError::TooFewVertices));
+        
+        // Close points separated in x order by another point are still welded
+        let interleaved = [Vec2::new(0.0, 0.0), Vec2::new(0.001, 1.0), Vec2::new(0.002, 0.0), Vec2::new(1.0, 0.0)];
+        assert_eq!(Polygon::convex_hull(&interleaved).unwrap().vertices.len(), 3);
     }
 }
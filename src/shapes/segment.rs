@@ -0,0 +1,58 @@
+use crate::math::{Vec2, Cross};
+use crate::math::Bounds;
+use crate::world::Transform;
+
+/// A two-sided line segment between two points.
+///
+/// A segment has no area, and hence no mass; a body with a segment shape is always static.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    pub a: Vec2,
+    pub b: Vec2,
+}
+
+impl Segment {
+    pub fn new(a: Vec2, b: Vec2) -> Segment {
+        Segment {
+            a,
+            b,
+        }
+    }
+    
+    /// Returns the normal of the side to the right of the direction from `a` to `b`.
+    #[inline]
+    pub fn normal(&self) -> Vec2 {
+        (self.b - self.a).normalized().cross(1.0)
+    }
+    
+    pub fn into_shape(self) -> super::Shape {
+        super::Shape::Segment(self)
+    }
+}
+
+impl super::Support for Segment {
+    fn support_point(&self, dir: &Vec2) -> Vec2 {
+        if self.a.dot(dir) >= self.b.dot(dir) { self.a } else { self.b }
+    }
+    
+    fn radius(&self) -> f32 {
+        0.0
+    }
+}
+
+impl super::Matter for Segment {
+    fn mass_and_inertia(&self, _density: f32) -> (f32, f32) {
+        (0.0, 0.0)
+    }
+    
+    fn center_of_mass(&self) -> Vec2 {
+        (self.a + self.b) * 0.5
+    }
+    
+    fn bounds(&self, transform: Option<&Transform>) -> Bounds {
+        let (a, b) = if let Some(t) = transform { (t.world_pos(&self.a), t.world_pos(&self.b)) } else { (self.a, self.b) };
+        
+        Bounds::new(a.min(&b), a.max(&b))
+    }
+}
@@ -1,3 +1,4 @@
+#[derive(Clone)]
 struct PoolObject<T: Default> {
     data: T,
     next: PoolId,
@@ -14,6 +15,7 @@ impl<T: Default> PoolObject<T> {
 
 pub type PoolId = usize;
 
+#[derive(Clone)]
 pub struct Pool<T: Default> {
     object_pool: Vec<PoolObject<T>>,
     pub object_count: usize,
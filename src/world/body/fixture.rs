@@ -0,0 +1,118 @@
+use crate::shapes::{Shape, Matter};
+use crate::world::Transform;
+use crate::math::{Bounds, Vec2};
+use crate::collision::broad_phase;
+use super::Material;
+
+/// Decides which fixtures can collide with each other.
+///
+/// Two fixtures collide if each one's category is in the other's mask. Fixtures with the same
+/// non-zero group always collide if the group is positive and never collide if it is negative,
+/// regardless of their categories and masks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Filter {
+    /// The categories this fixture belongs to, usually a single bit.
+    pub category_bits: u16,
+    /// The categories this fixture collides with.
+    pub mask_bits: u16,
+    pub group_index: i16,
+}
+
+impl Default for Filter {
+    /// A filter in the first category that collides with every category and has no group.
+    fn default() -> Filter {
+        Filter {
+            category_bits: 0x0001,
+            mask_bits: 0xffff,
+            group_index: 0,
+        }
+    }
+}
+
+impl Filter {
+    pub fn new(category_bits: u16, mask_bits: u16, group_index: i16) -> Filter {
+        Filter {
+            category_bits,
+            mask_bits,
+            group_index,
+        }
+    }
+    
+    /// Whether fixtures with this filter and `other` can collide.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Filter;
+    /// let player = Filter::new(0x0002, 0xfffd, 0);
+    /// let ground = Filter::default();
+    ///
+    /// assert!(player.should_collide(&ground));
+    /// assert!(!player.should_collide(&player));
+    /// assert!(Filter::new(0x0002, 0x0000, 1).should_collide(&Filter::new(0x0004, 0x0000, 1)));
+    /// ```
+    pub fn should_collide(&self, other: &Filter) -> bool {
+        if self.group_index == other.group_index && self.group_index != 0 {
+            return self.group_index > 0;
+        }
+        
+        (self.mask_bits & other.category_bits) != 0 && (self.category_bits & other.mask_bits) != 0
+    }
+}
+
+/// A shape attached to a `Body`, with its placement on the body and its physical properties.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixture {
+    pub shape: Shape,
+    /// The transform of the shape relative to the body.
+    pub transform: Transform,
+    pub density: f32,
+    pub material: Material,
+    pub filter: Filter,
+    /// The world space bounds of the shape, updated at the start of every step.
+    pub bounds: Bounds,
+    
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) proxy_id: broad_phase::ProxyId,
+}
+
+impl Fixture {
+    /// Creates a fixture at the body origin that collides with everything.
+    pub fn new(shape: Shape, density: f32, material: Material) -> Fixture {
+        let transform = Transform::new(Vec2::ZERO, 0.0);
+        let bounds = shape.bounds(Some(&transform));
+        
+        Fixture {
+            shape,
+            transform,
+            density,
+            material,
+            filter: Filter::default(),
+            bounds,
+            proxy_id: broad_phase::ProxyId::default(),
+        }
+    }
+    
+    /// Returns the mass of the fixture and its inertia about the body origin.
+    pub fn mass_and_inertia(&self) -> (f32, f32) {
+        let (mass, inertia) = self.shape.mass_and_inertia(self.density);
+        
+        // Parallel axis theorem, moving the inertia from the shape origin to the shape centroid
+        // and then to the body origin
+        let shape_center = self.shape.center_of_mass();
+        let center = self.center_of_mass();
+        
+        (mass, inertia - mass * shape_center.sqr_len() + mass * center.sqr_len())
+    }
+    
+    /// Returns the center of mass of the fixture relative to the body origin.
+    pub fn center_of_mass(&self) -> Vec2 {
+        self.transform.world_pos(&self.shape.center_of_mass())
+    }
+    
+    /// Updates the world space bounds of the fixture for a body with transform `body_transform`.
+    pub(crate) fn update_bounds(&mut self, body_transform: &Transform) {
+        self.bounds = self.shape.bounds(Some(&body_transform.world_transform(&self.transform)));
+    }
+}
@@ -0,0 +1,268 @@
+use fnv::FnvHashMap;
+
+use crate::math::Vec2;
+
+/// The identifier of a kind of material, such as ice or rubber, used for looking up the overrides
+/// set with `World::set_material_pair`.
+pub type MaterialId = u32;
+
+/// How the friction or restitution of two materials in contact are combined.
+///
+/// If the two materials have different rules, the rule that comes later in this list is used,
+/// so a material can make itself slippery with `Min` against any rule but `Max`, and bouncy with
+/// `Max` against any rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CombineRule {
+    /// The arithmetic mean of the two values. The default for restitution.
+    Average,
+    /// The square root of the product of the two values. The default for friction.
+    GeometricMean,
+    Multiply,
+    Min,
+    Max,
+}
+
+impl CombineRule {
+    /// Combines the values `a` and `b` of two materials.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::CombineRule;
+    /// assert_eq!(CombineRule::Average.combine(0.2, 0.6), 0.4);
+    /// assert_eq!(CombineRule::GeometricMean.combine(0.2, 0.8), 0.4);
+    /// assert_eq!(CombineRule::Min.combine(0.2, 0.6), 0.2);
+    /// ```
+    pub fn combine(self, a: f32, b: f32) -> f32 {
+        match self {
+            CombineRule::Average => 0.5 * (a + b),
+            CombineRule::GeometricMean => (a * b).sqrt(),
+            CombineRule::Min => a.min(b),
+            CombineRule::Multiply => a * b,
+            CombineRule::Max => a.max(b),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_friction_combine() -> CombineRule {
+    CombineRule::GeometricMean
+}
+
+#[cfg(feature = "serde")]
+fn default_restitution_combine() -> CombineRule {
+    CombineRule::Average
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Material {
+    pub restitution: f32,
+    pub friction: f32,
+    /// Resists rolling, as the torque about the contact per normal force and radius of the rolling
+    /// shape. Only rounded shapes roll, so it has no effect between shapes without a radius.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rolling_friction: f32,
+    
+    /// The kind of the material, for overriding the friction and restitution between pairs of
+    /// kinds. Defaults to 0.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: MaterialId,
+    #[cfg_attr(feature = "serde", serde(default = "default_friction_combine"))]
+    pub friction_combine: CombineRule,
+    #[cfg_attr(feature = "serde", serde(default = "default_restitution_combine"))]
+    pub restitution_combine: CombineRule,
+    
+    /// The speed at which the surface moves along itself, clockwise around the shape, like a
+    /// conveyor belt. A positive speed carries bodies resting on top of the shape to the right.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub surface_velocity: f32,
+    /// The friction coefficients for sliding along the local x and y axes of the fixture, used
+    /// instead of `friction` if set. Sliding in other directions blends the two, so a skate blade
+    /// along the x axis has a low x and a high y coefficient.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub anisotropic_friction: Option<Vec2>,
+}
+
+impl Default for Material {
+    fn default() -> Material {
+        Material::new(0.0, 0.0)
+    }
+}
+
+impl Material {
+    pub fn new(friction: f32, restitution: f32) -> Material {
+        Material {
+            restitution,
+            friction,
+            rolling_friction: 0.0,
+            id: 0,
+            friction_combine: CombineRule::GeometricMean,
+            restitution_combine: CombineRule::Average,
+            surface_velocity: 0.0,
+            anisotropic_friction: None,
+        }
+    }
+    
+    /// Returns this material with the given rolling friction.
+    pub fn with_rolling_friction(mut self, rolling_friction: f32) -> Material {
+        self.rolling_friction = rolling_friction;
+        self
+    }
+    
+    /// Returns this material with the given id.
+    pub fn with_id(mut self, id: MaterialId) -> Material {
+        self.id = id;
+        self
+    }
+    
+    /// Returns this material with the given rules for combining its friction and restitution with
+    /// those of other materials.
+    pub fn with_combine_rules(mut self, friction_combine: CombineRule, restitution_combine: CombineRule) -> Material {
+        self.friction_combine = friction_combine;
+        self.restitution_combine = restitution_combine;
+        self
+    }
+    
+    /// Returns this material with the given surface velocity.
+    pub fn with_surface_velocity(mut self, surface_velocity: f32) -> Material {
+        self.surface_velocity = surface_velocity;
+        self
+    }
+    
+    /// Returns this material with the given friction coefficients along the local x and y axes.
+    pub fn with_anisotropic_friction(mut self, friction: Vec2) -> Material {
+        self.anisotropic_friction = Some(friction);
+        self
+    }
+    
+    /// Returns the friction coefficient for sliding along `local_dir`, a unit vector in the local
+    /// space of the fixture.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{Material, Vec2};
+    /// let blade = Material::new(0.5, 0.0).with_anisotropic_friction(Vec2::new(0.02, 0.8));
+    ///
+    /// assert_eq!(blade.friction_along(&Vec2::RIGHT), 0.02);
+    /// assert_eq!(blade.friction_along(&Vec2::UP), 0.8);
+    /// assert_eq!(Material::new(0.5, 0.0).friction_along(&Vec2::UP), 0.5);
+    /// ```
+    pub fn friction_along(&self, local_dir: &Vec2) -> f32 {
+        match self.anisotropic_friction {
+            // The friction ellipse with the given coefficients as its semi-axes
+            Some(friction) => Vec2::new(friction.x * local_dir.x, friction.y * local_dir.y).len(),
+            None => self.friction,
+        }
+    }
+    
+    /// Returns the friction and restitution of contacts between materials `a` and `b`, each
+    /// combined with the rule of the material whose rule has precedence.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{Material, CombineRule};
+    /// let rubber = Material::new(0.9, 0.8);
+    /// let ice = Material::new(0.1, 0.2).with_combine_rules(CombineRule::Min, CombineRule::Average);
+    ///
+    /// let pair = Material::combine(&rubber, &ice);
+    /// assert_eq!(pair.friction, 0.1);
+    /// assert_eq!(pair.restitution, 0.5);
+    /// ```
+    pub fn combine(a: &Material, b: &Material) -> MaterialPair {
+        let friction_combine = a.friction_combine.max(b.friction_combine);
+        let restitution_combine = a.restitution_combine.max(b.restitution_combine);
+        
+        MaterialPair::new(friction_combine.combine(a.friction, b.friction),
+                          restitution_combine.combine(a.restitution, b.restitution))
+    }
+}
+
+/// The friction and restitution of contacts between two materials.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaterialPair {
+    pub friction: f32,
+    pub restitution: f32,
+}
+
+impl MaterialPair {
+    pub fn new(friction: f32, restitution: f32) -> MaterialPair {
+        MaterialPair {
+            friction,
+            restitution,
+        }
+    }
+}
+
+/// Overrides of the friction and restitution between pairs of material ids, in either order.
+#[derive(Clone, Default)]
+pub(crate) struct MaterialPairs {
+    pairs: FnvHashMap<(MaterialId, MaterialId), MaterialPair>,
+}
+
+impl MaterialPairs {
+    fn key(a: MaterialId, b: MaterialId) -> (MaterialId, MaterialId) {
+        if a <= b { (a, b) } else { (b, a) }
+    }
+    
+    pub fn get(&self, a: MaterialId, b: MaterialId) -> Option<&MaterialPair> {
+        self.pairs.get(&MaterialPairs::key(a, b))
+    }
+    
+    pub fn insert(&mut self, a: MaterialId, b: MaterialId, pair: MaterialPair) -> Option<MaterialPair> {
+        self.pairs.insert(MaterialPairs::key(a, b), pair)
+    }
+    
+    pub fn remove(&mut self, a: MaterialId, b: MaterialId) -> Option<MaterialPair> {
+        self.pairs.remove(&MaterialPairs::key(a, b))
+    }
+    
+    /// Returns the overrides in ascending order of their material ids.
+    #[cfg(feature = "serde")]
+    pub fn sorted(&self) -> Vec<(MaterialId, MaterialId, MaterialPair)> {
+        let mut pairs: Vec<_> = self.pairs.iter().map(|(&(a, b), &pair)| (a, b, pair)).collect();
+        pairs.sort_unstable_by_key(|&(a, b, _)| (a, b));
+        pairs
+    }
+    
+    /// Returns the friction and restitution of contacts between materials `a` and `b`, from the
+    /// override for their ids if there is one.
+    pub fn mix(&self, a: &Material, b: &Material) -> MaterialPair {
+        match self.get(a.id, b.id) {
+            Some(pair) => *pair,
+            None => Material::combine(a, b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn material(friction: f32, friction_combine: CombineRule) -> Material {
+        Material {
+            friction_combine,
+            ..Material::new(friction, 0.0)
+        }
+    }
+    
+    #[test]
+    fn combine_rule_precedence() {
+        let cases = [
+            (CombineRule::Min, CombineRule::Multiply, 0.2),
+            (CombineRule::Min, CombineRule::GeometricMean, 0.2),
+            (CombineRule::Max, CombineRule::Min, 0.5),
+            (CombineRule::Multiply, CombineRule::Average, 0.1),
+        ];
+        
+        // The result does not depend on which material has which rule
+        for &(rule_a, rule_b, friction) in cases.iter() {
+            let pair = Material::combine(&material(0.2, rule_a), &material(0.5, rule_b));
+            assert!((pair.friction - friction).abs() < 1e-6);
+            
+            let pair = Material::combine(&material(0.5, rule_b), &material(0.2, rule_a));
+            assert!((pair.friction - friction).abs() < 1e-6);
+        }
+    }
+}
@@ -1,39 +1,38 @@
 mod pair;
+mod fixture;
+mod material;
 
 pub use self::pair::BodyPair;
+pub use self::fixture::{Fixture, Filter};
+pub use self::material::{Material, MaterialId, MaterialPair, CombineRule};
+pub(crate) use self::material::MaterialPairs;
 
 use crate::math::{Vec2, Cross};
-use crate::shapes::{Shape, Matter};
+use crate::shapes::Shape;
 use crate::world::Transform;
 use crate::math::Bounds;
 
-
-use crate::collision::broad_phase;
-
 /// The identifier used for looking up a `Body` in a `World`.
 ///
 /// An identifier is used to avoid the need for an `Rc` - the `Body` can be looked up on demand
 /// through the `World`.
 pub type BodyId = usize;
 
-#[derive(Default)]
-pub struct Material {
-    pub restitution: f32,
-    pub friction: f32,
-}
-
-impl Material {
-    pub fn new(friction: f32, restitution: f32) -> Material {
-        Material {
-            restitution,
-            friction,
-        }
-    }
+/// The part of a `Body` that changes as it is simulated.
+#[derive(Copy, Clone)]
+pub(crate) struct BodyState {
+    transform: Transform,
+    
+    velocity: Vec2,
+    angular_vel: f32,
+    
+    force: Vec2,
+    torque: f32,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Body {
     pub id: BodyId,
-    pub(crate) proxy_id: broad_phase::ProxyId,
     
     pub transform: Transform,
     
@@ -43,58 +42,282 @@ pub struct Body {
     force: Vec2,
     torque: f32,
     
-    pub mass: f32,
-    pub inertia: f32,
+    pub(crate) mass: f32,
+    pub(crate) inertia: f32,
     
-    pub inv_mass: f32,
-    pub inv_inertia: f32,
+    pub(crate) inv_mass: f32,
+    pub(crate) inv_inertia: f32,
     
-    pub shape: Shape,
-    pub bounds: Bounds,
+    /// Whether `set_static` was called, which keeps the body massless when its mass is reset.
+    #[cfg_attr(feature = "serde", serde(default))]
+    is_static: bool,
+    
+    /// Slows the linear velocity by this fraction per second, like drag.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub linear_damping: f32,
+    /// Slows the angular velocity by this fraction per second.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub angular_damping: f32,
+    /// The speed the center of mass is limited to, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_linear_speed: Option<f32>,
+    /// The angular speed the body is limited to, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_angular_speed: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fixed_rotation: bool,
     
-    pub material: Material,
+    /// The center of mass relative to the body origin.
+    local_center: Vec2,
+    
+    fixtures: Vec<Fixture>,
+    /// The union of the world space bounds of the fixtures, updated at the start of every step.
+    pub bounds: Bounds,
 }
 
 impl Body {
+    /// Creates a body with a single fixture at its origin.
     pub fn new(shape: Shape, density: f32, material: Material) -> Body {
-        let transform = Transform::new(Vec2::ZERO, 0.0);
-        let aabb = shape.bounds(Some(&transform));
-        let (mass, inertia) = shape.mass_and_inertia(density);
-        
-        let inv_mass = if mass != 0.0 { 1.0 / mass } else { 0.0f32 };
-        let inv_inertia = if inertia != 0.0 { 1.0 / inertia } else { 0.0f32 };
-        
-        Body {
+        Body::with_fixtures(vec![Fixture::new(shape, density, material)])
+    }
+    
+    /// Creates a body made of several fixtures. Its mass is the sum of those of its fixtures and
+    /// it rotates about their combined center of mass, wherever that is relative to the origin.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{Body, Fixture, Material, Transform, Vec2};
+    /// # use physics2d::shapes::{Circle, Polygon};
+    /// let handle = Polygon::new(vec![Vec2::new(-1.0, -0.1), Vec2::new(1.0, -0.1),
+    ///                                Vec2::new(1.0, 0.1), Vec2::new(-1.0, 0.1)]);
+    ///
+    /// let mut left = Fixture::new(Circle::new(0.5).into_shape(), 1.0, Material::default());
+    /// left.transform = Transform::new(Vec2::LEFT, 0.0);
+    ///
+    /// let mut right = left.clone();
+    /// right.transform = Transform::new(Vec2::RIGHT, 0.0);
+    ///
+    /// let dumbbell = Body::with_fixtures(vec![
+    ///     Fixture::new(handle.into_shape(), 1.0, Material::default()),
+    ///     left,
+    ///     right,
+    /// ]);
+    ///
+    /// assert_eq!(dumbbell.fixtures().len(), 3);
+    /// assert_eq!(dumbbell.local_center(), Vec2::ZERO);
+    /// ```
+    pub fn with_fixtures(fixtures: Vec<Fixture>) -> Body {
+        let mut body = Body {
             id: BodyId::default(),
-            proxy_id: broad_phase::ProxyId::default(),
-            transform,
+            transform: Transform::new(Vec2::ZERO, 0.0),
             velocity: Vec2::ZERO,
             angular_vel: 0.0,
             force: Vec2::ZERO,
             torque: 0.0,
             
-            mass,
-            inertia,
-            inv_mass,
-            inv_inertia,
+            mass: 0.0,
+            inertia: 0.0,
+            inv_mass: 0.0,
+            inv_inertia: 0.0,
+            is_static: false,
             
-            shape,
-            bounds: aabb,
-            material,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            max_linear_speed: None,
+            max_angular_speed: None,
+            fixed_rotation: false,
+            
+            local_center: Vec2::ZERO,
+            
+            fixtures,
+            bounds: Bounds::new(Vec2::ZERO, Vec2::ZERO),
+        };
+        
+        body.reset_mass_from_shape();
+        body.update_bounds();
+        
+        body
+    }
+    
+    pub fn mass(&self) -> f32 {
+        self.mass
+    }
+    
+    /// Returns the rotational inertia about the center of mass.
+    pub fn inertia(&self) -> f32 {
+        self.inertia
+    }
+    
+    /// Returns the inverse of the mass, or zero if the body is static.
+    pub fn inv_mass(&self) -> f32 {
+        self.inv_mass
+    }
+    
+    /// Returns the inverse of the inertia used by the solver, or zero if the body is static or its
+    /// rotation is fixed.
+    pub fn inv_inertia(&self) -> f32 {
+        self.inv_inertia
+    }
+    
+    /// Overrides the mass, the rotational inertia about the center of mass and the center of mass
+    /// relative to the body origin, instead of computing them from the fixtures. The origin stays in
+    /// place.
+    ///
+    /// Does nothing on a body made static by `set_static`, which stays massless so that it never
+    /// moves.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the mass or inertia is negative or not finite, or if the center
+    /// of mass is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{Body, Material, Vec2};
+    /// # use physics2d::shapes::Circle;
+    /// let mut body = Body::new(Circle::new(1.0).into_shape(), 1.0, Material::default());
+    ///
+    /// // A heavy keel keeps a boat upright
+    /// body.set_mass_properties(10.0, 4.0, Vec2::DOWN * 0.5);
+    ///
+    /// assert_eq!(body.mass(), 10.0);
+    /// assert_eq!(body.inv_inertia(), 0.25);
+    /// assert_eq!(body.world_center(), Vec2::DOWN * 0.5);
+    /// ```
+    pub fn set_mass_properties(&mut self, mass: f32, inertia: f32, local_center: Vec2) {
+        debug_assert!(valid_mass_properties(mass, inertia, local_center),
+                      "mass properties must be finite and non-negative");
+        
+        if self.is_static {
+            return;
         }
+        
+        let old_center = self.world_center();
+        
+        self.mass = mass;
+        self.inertia = inertia;
+        self.inv_mass = if mass != 0.0 { 1.0 / mass } else { 0.0 };
+        self.local_center = local_center;
+        self.update_inv_inertia();
+        
+        // The velocity is that of the center of mass, so it changes along with the center
+        self.velocity += self.angular_vel.cross(&(self.world_center() - old_center));
+    }
+    
+    /// Recomputes the mass, inertia and center of mass from the shapes and densities of the
+    /// fixtures, undoing `set_mass_properties`.
+    pub fn reset_mass_from_shape(&mut self) {
+        let (mass, inertia, local_center) = self.mass_from_shape();
+        self.set_mass_properties(mass, inertia, local_center);
     }
     
-    pub(crate) fn integrate_force(&mut self, dt: f32) {
-        // TODO: Make configurable
-        const GRAVITY: Vec2 = Vec2 { x: 0.0, y: -9.8 };
+    /// Returns the mass, inertia and center of mass computed from the fixtures.
+    pub(crate) fn mass_from_shape(&self) -> (f32, f32, Vec2) {
+        let mut mass = 0.0;
+        let mut moment = Vec2::ZERO;
+        let mut origin_inertia = 0.0;
+        
+        for fixture in self.fixtures.iter() {
+            let (fixture_mass, fixture_inertia) = fixture.mass_and_inertia();
+            
+            mass += fixture_mass;
+            moment += fixture.center_of_mass() * fixture_mass;
+            origin_inertia += fixture_inertia;
+        }
         
+        let local_center = if mass != 0.0 { moment / mass } else { Vec2::ZERO };
+        
+        // Parallel axis theorem, from the body origin to the center of mass. Rounding may leave a
+        // point mass with a slightly negative inertia
+        let inertia = (origin_inertia - mass * local_center.sqr_len()).max(0.0);
+        
+        (mass, inertia, local_center)
+    }
+    
+    /// Sets the density of every fixture and recomputes the mass of the body.
+    pub fn set_density(&mut self, density: f32) {
+        for fixture in self.fixtures.iter_mut() {
+            fixture.density = density;
+        }
+        
+        self.reset_mass_from_shape();
+    }
+    
+    /// Replaces the shape of the first fixture and recomputes the mass and bounds of the body.
+    /// `World::set_shape` calls this and updates the broad phase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body has no fixtures.
+    pub(crate) fn set_shape(&mut self, shape: Shape) {
+        self.fixtures[0].shape = shape;
+        
+        self.reset_mass_from_shape();
+        self.update_bounds();
+    }
+    
+    fn update_inv_inertia(&mut self) {
+        self.inv_inertia = if self.fixed_rotation || self.inertia == 0.0 { 0.0 } else { 1.0 / self.inertia };
+    }
+    
+    /// Returns the center of mass relative to the body origin.
+    pub fn local_center(&self) -> Vec2 {
+        self.local_center
+    }
+    
+    /// Returns the center of mass in world space.
+    pub fn world_center(&self) -> Vec2 {
+        self.transform.world_pos(&self.local_center)
+    }
+    
+    /// Moves the body so that its center of mass is at `center`, with the given rotation about
+    /// the center of mass.
+    pub(crate) fn set_world_center(&mut self, center: Vec2, rotation: f32) {
+        self.transform.set_rotation(rotation);
+        self.transform.position = center - self.transform.world_dir(&self.local_center);
+    }
+    
+    pub fn fixtures(&self) -> &[Fixture] {
+        &self.fixtures
+    }
+    
+    /// Returns the fixtures for modification. Changing their shapes or densities updates neither
+    /// the mass of the body, until `reset_mass_from_shape` is called, nor the broad phase.
+    pub(crate) fn fixtures_mut(&mut self) -> &mut [Fixture] {
+        &mut self.fixtures
+    }
+    
+    /// Replaces the material of the fixture at `fixture_idx`. Contacts use the new friction and
+    /// restitution from the next step on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body has no fixture at `fixture_idx`.
+    pub fn set_material(&mut self, fixture_idx: usize, material: Material) {
+        self.fixtures[fixture_idx].material = material;
+    }
+    
+    /// Returns the shape of the first fixture.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body has no fixtures.
+    pub fn shape(&self) -> &Shape {
+        &self.fixtures[0].shape
+    }
+    
+    pub(crate) fn integrate_force(&mut self, gravity: Vec2, dt: f32) {
         if self.is_static() {
             return;
         }
         
-        self.velocity += (GRAVITY + self.force * self.inv_mass) * dt;
+        self.velocity += (gravity + self.force * self.inv_mass) * dt;
         self.angular_vel += self.torque * self.inv_inertia * dt;
         
+        // Implicit damping, which is stable for any time step
+        self.velocity *= 1.0 / (1.0 + dt * self.linear_damping);
+        self.angular_vel *= 1.0 / (1.0 + dt * self.angular_damping);
+        
         self.force = Vec2::ZERO;
         self.torque = 0.0;
     }
@@ -104,18 +327,79 @@ impl Body {
             return;
         }
         
-        self.transform.position += self.velocity * dt;
+        if let Some(max_speed) = self.max_linear_speed {
+            let speed = self.velocity.len();
+            
+            if speed > max_speed {
+                self.velocity *= max_speed / speed;
+            }
+        }
+        
+        if let Some(max_speed) = self.max_angular_speed {
+            self.angular_vel = self.angular_vel.max(-max_speed).min(max_speed);
+        }
         
+        // The velocity is that of the center of mass, which the body rotates about
+        let new_center = self.world_center() + self.velocity * dt;
         let new_rotation = self.transform.rotation() + self.angular_vel * dt;
         
-        self.transform.set_rotation(new_rotation);
+        self.set_world_center(new_center, new_rotation);
     }
     
     pub(crate) fn update(&mut self, _dt: f32) {
-        self.bounds = self.shape.bounds(Some(&self.transform));
+        self.update_bounds();
+    }
+    
+    pub(crate) fn update_bounds(&mut self) {
+        let transform = self.transform;
+        let mut bounds: Option<Bounds> = None;
+        
+        for fixture in self.fixtures.iter_mut() {
+            fixture.update_bounds(&transform);
+            bounds = Some(bounds.map_or(fixture.bounds, |bounds| bounds.union(&fixture.bounds)));
+        }
+        
+        self.bounds = bounds.unwrap_or_else(|| Bounds::new(transform.position, transform.position));
+    }
+    
+    pub(crate) fn state(&self) -> BodyState {
+        BodyState {
+            transform: self.transform,
+            velocity: self.velocity,
+            angular_vel: self.angular_vel,
+            force: self.force,
+            torque: self.torque,
+        }
+    }
+    
+    pub(crate) fn set_state(&mut self, state: &BodyState) {
+        self.transform = state.transform;
+        self.velocity = state.velocity;
+        self.angular_vel = state.angular_vel;
+        self.force = state.force;
+        self.torque = state.torque;
+        
+        self.update_bounds();
+    }
+    
+    /// Returns whether the body is prevented from rotating.
+    pub fn fixed_rotation(&self) -> bool {
+        self.fixed_rotation
+    }
+    
+    /// Prevents the body from rotating, such as to keep a character upright, or allows it to
+    /// rotate again. The body keeps its `inertia`, but the solver treats it as infinite.
+    pub fn set_fixed_rotation(&mut self, fixed_rotation: bool) {
+        self.fixed_rotation = fixed_rotation;
+        self.update_inv_inertia();
+        
+        if fixed_rotation {
+            self.angular_vel = 0.0;
+        }
     }
     
     pub fn set_static(&mut self) {
+        self.is_static = true;
         self.inv_inertia = 0.0;
         self.inertia = 0.0;
         self.mass = 0.0;
@@ -134,13 +418,22 @@ impl Body {
         self.torque += torque;
     }
     
+    /// Applies `force` at the world space position `pos`, which also applies a torque about the
+    /// center of mass.
     pub fn add_force_at_pos(&mut self, force: Vec2, pos: Vec2) {
         self.add_force(force);
-        self.add_torque(pos.cross(force));
+        self.add_torque((pos - self.world_center()).cross(force));
     }
     
+    /// Applies `impulse` at `pos`, relative to the center of mass in world space.
     pub fn add_impulse_at_pos(&mut self, impulse: Vec2, pos: Vec2) {
         self.velocity += impulse * self.inv_mass;
         self.angular_vel += pos.cross(impulse) * self.inv_inertia;
     }
 }
+
+/// Returns whether the mass, inertia and center of mass can be given to `Body::set_mass_properties`.
+pub(crate) fn valid_mass_properties(mass: f32, inertia: f32, local_center: Vec2) -> bool {
+    mass.is_finite() && mass >= 0.0 && inertia.is_finite() && inertia >= 0.0 &&
+        local_center.x.is_finite() && local_center.y.is_finite()
+}
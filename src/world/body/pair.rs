@@ -1,7 +1,7 @@
 use super::{Body, BodyId};
 use crate::world::Bodies;
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct BodyPair(pub BodyId, pub BodyId);
 
 impl BodyPair {
@@ -92,64 +92,99 @@ impl IndexMut<BodyId> for Bodies {
 
 pub type ConstraintsMap<T> = FnvHashMap<BodyPair, Vec<T>>;
 
+/// Calls `f` for every body pair in `map`, in the given `order` if any, or in the map's
+/// iteration order otherwise.
+fn for_each_pair<T, F>(map: &mut ConstraintsMap<T>, order: Option<&[BodyPair]>, mut f: F)
+    where F: FnMut(&BodyPair, &mut Vec<T>) {
+    match order {
+        Some(pairs) => {
+            for body_pair in pairs.iter() {
+                if let Some(constraints) = map.get_mut(body_pair) {
+                    f(body_pair, constraints);
+                }
+            }
+        }
+        None => {
+            for (body_pair, constraints) in map.iter_mut() {
+                f(body_pair, constraints);
+            }
+        }
+    }
+}
+
 // TODO: Rename
+/// Solves all the constraints in a map.
+///
+/// Every method takes an optional `order` of body pairs. When given, constraints are solved in
+/// that order instead of the map's iteration order, which depends on the history of insertions
+/// and removals.
 pub trait ConstraintSolverMap {
-    fn initialize_velocity(&mut self, body_map: &Bodies, dt: f32);
+    /// Returns the body pairs in the map in ascending order.
+    fn sorted_pairs(&self) -> Vec<BodyPair>;
+    
+    fn initialize_velocity(&mut self, body_map: &Bodies, order: Option<&[BodyPair]>, dt: f32);
     
-    fn warm_start_velocity(&mut self, body_map: &mut Bodies, dt: f32);
-    fn warm_start_position(&mut self, body_map: &mut Bodies, dt: f32);
+    fn warm_start_velocity(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32);
+    fn warm_start_position(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32);
     
-    fn solve_velocity(&mut self, body_map: &mut Bodies, dt: f32);
-    fn solve_position(&mut self, body_map: &mut Bodies, dt: f32);
+    fn solve_velocity(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32);
+    fn solve_position(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32);
 }
 
 impl<T: Constraint> ConstraintSolverMap for ConstraintsMap<T> {
-    fn initialize_velocity(&mut self, body_map: &Bodies, dt: f32) {
-        for (body_pair, constraints) in self.iter_mut() {
+    fn sorted_pairs(&self) -> Vec<BodyPair> {
+        let mut pairs: Vec<BodyPair> = self.keys().cloned().collect();
+        pairs.sort_unstable();
+        pairs
+    }
+    
+    fn initialize_velocity(&mut self, body_map: &Bodies, order: Option<&[BodyPair]>, dt: f32) {
+        for_each_pair(self, order, |body_pair, constraints| {
             let (body_a, body_b) = body_pair.as_ref(body_map);
             
             for constraint in constraints.iter_mut() {
                 constraint.initialize_velocity(body_a, body_b, dt);
             }
-        }
+        });
     }
     
-    fn warm_start_velocity(&mut self, body_map: &mut Bodies, dt: f32) {
-        for (body_pair, constraints) in self.iter_mut() {
+    fn warm_start_velocity(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32) {
+        for_each_pair(self, order, |body_pair, constraints| {
             let (body_a, body_b) = body_pair.as_mut(body_map);
             
             for constraint in constraints.iter_mut() {
                 constraint.warm_start_velocity(body_a, body_b, dt)
             }
-        }
+        });
     }
     
-    fn warm_start_position(&mut self, body_map: &mut Bodies, dt: f32) {
-        for (body_pair, constraints) in self.iter_mut() {
+    fn warm_start_position(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32) {
+        for_each_pair(self, order, |body_pair, constraints| {
             let (body_a, body_b) = body_pair.as_mut(body_map);
             
             for constraint in constraints.iter_mut() {
                 constraint.warm_start_position(body_a, body_b, dt)
             }
-        }
+        });
     }
     
-    fn solve_velocity(&mut self, body_map: &mut Bodies, dt: f32) {
-        for (body_pair, constraints) in self.iter_mut() {
+    fn solve_velocity(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32) {
+        for_each_pair(self, order, |body_pair, constraints| {
             let (body_a, body_b) = body_pair.as_mut(body_map);
             
             for constraint in constraints.iter_mut() {
                 constraint.solve_velocity(body_a, body_b, dt)
             }
-        }
+        });
     }
-    fn solve_position(&mut self, body_map: &mut Bodies, dt: f32) {
-        for (body_pair, constraints) in self.iter_mut() {
+    
+    fn solve_position(&mut self, body_map: &mut Bodies, order: Option<&[BodyPair]>, dt: f32) {
+        for_each_pair(self, order, |body_pair, constraints| {
             let (body_a, body_b) = body_pair.as_mut(body_map);
             
             for constraint in constraints.iter_mut() {
                 constraint.solve_position(body_a, body_b, dt)
             }
-        }
+        });
     }
 }
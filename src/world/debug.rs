@@ -10,7 +10,7 @@ impl DebugCollision for World {
     fn contacts(&self) -> Vec<&Contact> {
         self.contact_constraints
             .values()
-            .flat_map(|constraints| constraints.iter().map(|constraint| &constraint.contact))
+            .flat_map(|manifolds| manifolds.iter().flat_map(|manifold| manifold.contacts()))
             .collect()
     }
 }
\ No newline at end of file
@@ -1,51 +1,98 @@
 mod transform;
 mod collections;
 mod body;
+mod snapshot;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "scene")]
+pub mod scene;
 pub mod debug;
 
-pub use self::body::{Body, BodyId, Material};
+#[cfg(test)]
+mod tests;
+
+pub use self::body::{Body, BodyId, Material, MaterialId, MaterialPair, CombineRule, Fixture, Filter};
 pub use self::transform::Transform;
-pub(crate) use self::body::BodyPair;
+pub use self::snapshot::WorldSnapshot;
+pub(crate) use self::body::{BodyPair, MaterialPairs, valid_mass_properties};
 pub(crate) use self::collections::{Bodies, ConstraintsMap};
 
 use self::collections::{ConstraintSolverMap};
-use crate::collision::{ContactConstraint, collide};
+use crate::collision::{ContactManifold, DistanceOutput, collide, distance_shapes};
 use crate::collision::broad_phase::{BroadPhase, BoundsTreeBroadPhase};
 use crate::joint::Joint;
+use crate::math::Vec2;
+use crate::shapes::Shape;
 
 pub struct World {
     bodies: Bodies,
     
     broad_phase: BoundsTreeBroadPhase,
     
-    contact_constraints: ConstraintsMap<ContactConstraint>,
+    contact_constraints: ConstraintsMap<ContactManifold>,
     joints: ConstraintsMap<Joint>,
     
+    material_pairs: MaterialPairs,
+    
+    /// The acceleration due to gravity applied to all non-static bodies.
+    pub gravity: Vec2,
+    
     pub velocity_iterations: u8,
     pub position_iterations: u8,
+    
+    /// Whether the simulation is deterministic.
+    ///
+    /// When `true`, contacts and joints are solved in ascending `BodyPair` order instead of hash
+    /// map iteration order, so the result of a step only depends on the state of the world and
+    /// not on the order in which bodies, joints and contacts were added or removed. Two worlds
+    /// built from identical inputs then produce bit-identical transforms on the same platform and
+    /// build, which is required for lockstep networking and replays.
+    ///
+    /// No solver step sums over an unordered collection, so ordering the constraints is the only
+    /// change needed. Sorting the pairs adds a small cost to every step. Defaults to `false`.
+    pub deterministic: bool,
 }
 
 impl Default for World {
     fn default() -> World {
-        World::new(8, 2)
+        World::new(World::DEFAULT_VELOCITY_ITERATIONS, World::DEFAULT_POSITION_ITERATIONS)
     }
 }
 
 impl World {
+    /// The default value of `World::gravity`.
+    pub const DEFAULT_GRAVITY: Vec2 = Vec2 { x: 0.0, y: -9.8 };
+    /// The number of velocity iterations of `World::default`.
+    pub const DEFAULT_VELOCITY_ITERATIONS: u8 = 8;
+    /// The number of position iterations of `World::default`.
+    pub const DEFAULT_POSITION_ITERATIONS: u8 = 2;
+    
     pub fn new(velocity_iterations: u8, position_iterations: u8) -> World {
         World {
             bodies: Bodies::default(),
             broad_phase: BoundsTreeBroadPhase::new(),
             contact_constraints: ConstraintsMap::default(),
             joints: ConstraintsMap::default(),
+            material_pairs: MaterialPairs::default(),
+            gravity: World::DEFAULT_GRAVITY,
             velocity_iterations,
             position_iterations,
+            deterministic: false,
         }
     }
     
     pub fn add_body(&mut self, mut body: Body) -> BodyId {
-        body.proxy_id = self.broad_phase.create_proxy(&body);
-        self.bodies.add(body)
+        body.update_bounds();
+        
+        let body_id = self.bodies.add(body);
+        let body = &mut self.bodies[body_id];
+        
+        for fixture_idx in 0..body.fixtures().len() {
+            let proxy_id = self.broad_phase.create_proxy(body, fixture_idx);
+            body.fixtures_mut()[fixture_idx].proxy_id = proxy_id;
+        }
+        
+        body_id
     }
     
     pub fn add_joint(&mut self, bodies: (BodyId, BodyId), joint: Joint) {
@@ -64,6 +111,35 @@ impl World {
         self.joints.get_mut(&bodies)
     }
     
+    /// Sets the friction and restitution of contacts between fixtures whose materials have the ids
+    /// `a` and `b`, in either order, instead of combining those of the materials. Returns the
+    /// previous override for the pair, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::{World, MaterialPair};
+    /// const ICE: u32 = 1;
+    /// const RUBBER: u32 = 2;
+    ///
+    /// let mut world = World::default();
+    /// world.set_material_pair(ICE, RUBBER, MaterialPair::new(0.05, 0.3));
+    ///
+    /// assert_eq!(world.get_material_pair(RUBBER, ICE), Some(&MaterialPair::new(0.05, 0.3)));
+    /// ```
+    pub fn set_material_pair(&mut self, a: MaterialId, b: MaterialId, pair: MaterialPair) -> Option<MaterialPair> {
+        self.material_pairs.insert(a, b, pair)
+    }
+    
+    pub fn get_material_pair(&self, a: MaterialId, b: MaterialId) -> Option<&MaterialPair> {
+        self.material_pairs.get(a, b)
+    }
+    
+    /// Removes the override for the materials with ids `a` and `b`, so that their friction and
+    /// restitution are combined again.
+    pub fn remove_material_pair(&mut self, a: MaterialId, b: MaterialId) -> Option<MaterialPair> {
+        self.material_pairs.remove(a, b)
+    }
+    
     pub fn get_body(&self, body_id: BodyId) -> &Body {
         &self.bodies[body_id]
     }
@@ -72,10 +148,71 @@ impl World {
         self.bodies.get_mut(body_id).unwrap()
     }
     
+    /// Replaces the shape of the first fixture of a body and recomputes its mass, such as to grow
+    /// or shrink it. The broad phase is updated at once, and the contacts of the body are no longer
+    /// warm started from those of the old shape.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body has no fixtures.
+    pub fn set_shape(&mut self, body_id: BodyId, shape: Shape) {
+        self.bodies.get_mut(body_id).unwrap().set_shape(shape);
+        
+        self.contact_constraints.retain(|pair, _| pair.0 != body_id && pair.1 != body_id);
+        self.reinsert_proxy(body_id, 0);
+    }
+    
+    /// Replaces the collision filter of the fixture at `fixture_idx` of a body. Contacts the new
+    /// filter rejects end, and those it allows begin, at the next step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body has no fixture at `fixture_idx`.
+    pub fn set_filter(&mut self, body_id: BodyId, fixture_idx: usize, filter: Filter) {
+        self.bodies.get_mut(body_id).unwrap().fixtures_mut()[fixture_idx].filter = filter;
+        self.reinsert_proxy(body_id, fixture_idx);
+    }
+    
+    /// Reinserts the proxy of a fixture, even if the fixture fits its bounds, so that the broad
+    /// phase looks for its pairs again.
+    fn reinsert_proxy(&mut self, body_id: BodyId, fixture_idx: usize) {
+        let body = self.bodies.get_mut(body_id).unwrap();
+        
+        self.broad_phase.destroy_proxy(body.fixtures()[fixture_idx].proxy_id);
+        let proxy_id = self.broad_phase.create_proxy(body, fixture_idx);
+        body.fixtures_mut()[fixture_idx].proxy_id = proxy_id;
+    }
+    
+    /// Returns the distance between the closest fixtures of two bodies and their closest points, or
+    /// `None` if either body has no fixtures.
+    ///
+    /// Chains and heightfields are measured segment by segment.
+    pub fn distance(&self, body_a: BodyId, body_b: BodyId) -> Option<DistanceOutput> {
+        let (a, b) = (self.get_body(body_a), self.get_body(body_b));
+        
+        let mut closest: Option<DistanceOutput> = None;
+        
+        for fixture_a in a.fixtures().iter() {
+            let transform_a = a.transform.world_transform(&fixture_a.transform);
+            
+            for fixture_b in b.fixtures().iter() {
+                let transform_b = b.transform.world_transform(&fixture_b.transform);
+                let output = distance_shapes(&fixture_a.shape, &transform_a, &fixture_b.shape, &transform_b);
+                
+                closest = match closest {
+                    Some(closest) if closest.distance <= output.distance => Some(closest),
+                    _ => Some(output),
+                };
+            }
+        }
+        
+        closest
+    }
+    
     pub fn bodies_iter(&self) -> impl Iterator<Item=&Body> {
         self.bodies.iter()
     }
-
+    
     pub fn body_count(&self) -> usize {
         self.bodies.len()
     }
@@ -83,7 +220,10 @@ impl World {
     pub fn update(&mut self, dt: f32) {
         for body in self.bodies.iter_mut() {
             body.update(dt);
-            self.broad_phase.update_proxy(body.proxy_id, body);
+            
+            for (fixture_idx, fixture) in body.fixtures().iter().enumerate() {
+                self.broad_phase.update_proxy(fixture.proxy_id, body, fixture_idx);
+            }
         }
         
         {
@@ -99,18 +239,23 @@ impl World {
         
         {
             let bodies = &self.bodies;
+            let material_pairs = &self.material_pairs;
             self.contact_constraints.retain(|pair, constraints| {
                 let body_a = &bodies[pair.0];
                 let body_b = &bodies[pair.1];
                 
                 if let Some(new_contacts) = collide(body_a, body_b) {
-                    let new_constraints =
+                    let mut new_constraints =
                         if !constraints.is_empty() {
-                            ContactConstraint::with_persistent_contacts(constraints, &new_contacts)
+                            ContactManifold::with_persistent_contacts(constraints, &new_contacts)
                         } else {
-                            ContactConstraint::with_contacts(&new_contacts)
+                            ContactManifold::with_contacts(&new_contacts)
                         };
                     
+                    for manifold in new_constraints.iter_mut() {
+                        manifold.set_material(body_a, body_b, material_pairs);
+                    }
+                    
                     *constraints = new_constraints;
                     
                     true
@@ -121,31 +266,40 @@ impl World {
         }
         
         for body in self.bodies.iter_mut() {
-            body.integrate_force(dt);
+            body.integrate_force(self.gravity, dt);
         }
         
-        self.contact_constraints.initialize_velocity(&mut self.bodies, dt);
-        self.contact_constraints.warm_start_velocity(&mut self.bodies, dt);
+        let (contact_order, joint_order) = if self.deterministic {
+            (Some(self.contact_constraints.sorted_pairs()), Some(self.joints.sorted_pairs()))
+        } else {
+            (None, None)
+        };
         
-        self.joints.initialize_velocity(&mut self.bodies, dt);
-        self.joints.warm_start_velocity(&mut self.bodies, dt);
+        let contact_order = contact_order.as_deref();
+        let joint_order = joint_order.as_deref();
+        
+        self.contact_constraints.initialize_velocity(&mut self.bodies, contact_order, dt);
+        self.contact_constraints.warm_start_velocity(&mut self.bodies, contact_order, dt);
+        
+        self.joints.initialize_velocity(&mut self.bodies, joint_order, dt);
+        self.joints.warm_start_velocity(&mut self.bodies, joint_order, dt);
         
         for _ in 0..self.velocity_iterations {
-            self.joints.solve_velocity(&mut self.bodies, dt);
+            self.joints.solve_velocity(&mut self.bodies, joint_order, dt);
             
-            self.contact_constraints.solve_velocity(&mut self.bodies, dt);
+            self.contact_constraints.solve_velocity(&mut self.bodies, contact_order, dt);
         }
         
         for body in self.bodies.iter_mut() {
             body.integrate_velocity(dt);
         }
         
-        self.contact_constraints.warm_start_position(&mut self.bodies, dt);
-    
+        self.contact_constraints.warm_start_position(&mut self.bodies, contact_order, dt);
+        
         for _ in 0..self.position_iterations {
-            self.joints.solve_position(&mut self.bodies, dt);
+            self.joints.solve_position(&mut self.bodies, joint_order, dt);
             
-            self.contact_constraints.solve_position(&mut self.bodies, dt);
+            self.contact_constraints.solve_position(&mut self.bodies, contact_order, dt);
         }
     }
 }
@@ -0,0 +1,887 @@
+//! A versioned JSON scene format for describing worlds in data files.
+//!
+//! A scene lists named bodies with their shapes and materials, the joints between them
+//! (referencing bodies by name), gravity and solver settings. Scenes are read and written with
+//! `World::load_scene` and `World::save_scene`, or manipulated directly through `Scene`.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "gravity": { "x": 0.0, "y": -9.8 },
+//!   "bodies": [
+//!     {
+//!       "name": "ground",
+//!       "shape": { "type": "polygon", "vertices": [
+//!         { "x": -10.0, "y": -0.5 }, { "x": 10.0, "y": -0.5 },
+//!         { "x": 10.0, "y": 0.5 }, { "x": -10.0, "y": 0.5 }
+//!       ] },
+//!       "static": true
+//!     },
+//!     {
+//!       "name": "ball",
+//!       "shape": { "type": "circle", "radius": 1.0 },
+//!       "density": 10.0,
+//!       "material": { "friction": 0.3, "restitution": 0.6 },
+//!       "position": { "x": 0.0, "y": 5.0 }
+//!     }
+//!   ],
+//!   "joints": [
+//!     { "type": "spring", "body_a": "ground", "body_b": "ball", "frequency": 1.5, "damping": 0.7 }
+//!   ]
+//! }
+//! ```
+//!
+//! All body fields except `name` are optional, as are `gravity`, `solver`, `joints` and
+//! `material_pairs`. A material may also have a `rolling_friction`, an `id`, a `surface_velocity`,
+//! an `anisotropic_friction` vector and the `friction_combine` and `restitution_combine` rules,
+//! which default to zero, zero, zero, none, `"GeometricMean"` and `"Average"`. A body may also
+//! have a `linear_damping` and `angular_damping`, which default to zero, a `max_linear_speed` and
+//! `max_angular_speed`, unlimited if omitted, and `fixed_rotation` to keep it from rotating. Its
+//! `mass`, `inertia` about the center of mass and `local_center` of mass are computed from its
+//! shapes unless given.
+//!
+//! `material_pairs` overrides the friction and restitution between materials by their ids:
+//!
+//! ```json
+//! "material_pairs": [{ "material_a": 1, "material_b": 2, "friction": 0.05, "restitution": 0.3 }]
+//! ```
+//!
+//! A body made of several shapes lists them in `fixtures`, each with its own `shape` and an
+//! optional `position` and `rotation` relative to the body, `density`, `material` and `filter`:
+//!
+//! ```json
+//! {
+//!   "name": "hammer",
+//!   "fixtures": [
+//!     { "shape": { "type": "capsule", "half_length": 1.0, "radius": 0.1 } },
+//!     { "shape": { "type": "circle", "radius": 0.3 }, "position": { "x": 1.0, "y": 0.0 },
+//!       "density": 5.0, "filter": { "category_bits": 2, "mask_bits": 65535, "group_index": 0 } }
+//!   ]
+//! }
+//! ```
+//!
+//! The `shape`, `density` and `material` of a body describe one more fixture at the body origin,
+//! added before those in `fixtures`.
+
+use std::collections::HashMap;
+use std::{error, fmt, io};
+
+use serde::{Serialize, Deserialize};
+
+use crate::math::Vec2;
+use crate::shapes::{Shape, Circle, Polygon, PolygonError, Capsule, Ellipse, Segment, Chain, Heightfield, HeightfieldError};
+use crate::world::{World, Body, BodyId, Material, MaterialId, MaterialPair, Fixture, Filter, Transform,
+                   valid_mass_properties};
+use crate::joint::{Joint, SpringJoint};
+
+/// An error that occurred while loading or saving a scene.
+#[derive(Debug)]
+pub enum SceneError {
+    /// The scene could not be read, written or parsed.
+    Json(serde_json::Error),
+    /// The scene was written in a version of the format that is not supported.
+    UnsupportedVersion(u32),
+    /// More than one body in the scene has the given name.
+    DuplicateBodyName(String),
+    /// A joint references a body name that does not exist in the scene.
+    UnknownBody(String),
+    /// The body with the given name has a polygon whose vertices do not form a valid polygon.
+    InvalidPolygon(String, PolygonError),
+    /// The body with the given name has a chain with too few vertices.
+    InvalidChain(String),
+    /// The body with the given name has a heightfield with invalid heights or spacing.
+    InvalidHeightfield(String, HeightfieldError),
+    /// The body with the given name has a negative or non-finite mass, inertia or center of mass.
+    InvalidMass(String),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SceneError::Json(ref error) => write!(f, "invalid scene: {}", error),
+            SceneError::UnsupportedVersion(version) => write!(f, "unsupported scene version {}", version),
+            SceneError::DuplicateBodyName(ref name) => write!(f, "duplicate body name '{}'", name),
+            SceneError::UnknownBody(ref name) => write!(f, "unknown body '{}'", name),
+            SceneError::InvalidPolygon(ref name, ref error) => write!(f, "invalid polygon in body '{}': {}", name, error),
+            SceneError::InvalidChain(ref name) => write!(f, "chain with too few vertices in body '{}'", name),
+            SceneError::InvalidHeightfield(ref name, ref error) =>
+                write!(f, "invalid heightfield in body '{}': {}", name, error),
+            SceneError::InvalidMass(ref name) => write!(f, "invalid mass properties of body '{}'", name),
+        }
+    }
+}
+
+impl error::Error for SceneError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            SceneError::Json(ref error) => Some(error),
+            SceneError::InvalidPolygon(_, ref error) => Some(error),
+            SceneError::InvalidHeightfield(_, ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for SceneError {
+    fn from(error: serde_json::Error) -> SceneError {
+        SceneError::Json(error)
+    }
+}
+
+/// The shape of a body in a scene.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SceneShape {
+    Circle {
+        radius: f32,
+    },
+    /// A convex polygon with counter-clockwise vertices. Scenes with invalid polygons fail to load
+    /// with `SceneError::InvalidPolygon`.
+    Polygon {
+        vertices: Vec<Vec2>,
+        /// The radius that rounds the corners of the polygon, outside its vertices.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        radius: f32,
+    },
+    /// A capsule along the local x axis.
+    Capsule {
+        half_length: f32,
+        radius: f32,
+    },
+    /// An ellipse with its axes along the local x and y axes.
+    Ellipse {
+        half_extents: Vec2,
+    },
+    /// A two-sided segment. Bodies with segment shapes are always static.
+    Segment {
+        a: Vec2,
+        b: Vec2,
+    },
+    /// An open or closed polyline of segments, with at least 2 vertices or 3 if closed. Bodies
+    /// with chain shapes are always static.
+    Chain {
+        vertices: Vec<Vec2>,
+        #[serde(default)]
+        closed: bool,
+    },
+    /// Regularly spaced heights for terrain. Bodies with heightfield shapes are always static.
+    Heightfield {
+        heights: Vec<f32>,
+        scale: Vec2,
+    },
+}
+
+impl SceneShape {
+    /// Builds the shape of the body named `body_name`.
+    fn to_shape(&self, body_name: &str) -> Result<Shape, SceneError> {
+        let shape = match *self {
+            SceneShape::Circle { radius } => Circle::new(radius).into_shape(),
+            SceneShape::Polygon { ref vertices, radius } => {
+                let mut polygon = Polygon::try_new(vertices.clone())
+                    .map_err(|error| SceneError::InvalidPolygon(body_name.to_string(), error))?;
+                polygon.radius = radius;
+                polygon.into_shape()
+            },
+            SceneShape::Capsule { half_length, radius } => Capsule::new(half_length, radius).into_shape(),
+            SceneShape::Ellipse { half_extents } => Ellipse::new(half_extents).into_shape(),
+            SceneShape::Segment { a, b } => Segment::new(a, b).into_shape(),
+            SceneShape::Chain { ref vertices, closed } => {
+                if vertices.len() < Chain::min_vertices(closed) {
+                    return Err(SceneError::InvalidChain(body_name.to_string()));
+                }
+                
+                if closed {
+                    Chain::new_loop(vertices.clone()).into_shape()
+                } else {
+                    Chain::new(vertices.clone()).into_shape()
+                }
+            },
+            SceneShape::Heightfield { ref heights, scale } => Heightfield::try_new(heights.clone(), scale)
+                .map_err(|error| SceneError::InvalidHeightfield(body_name.to_string(), error))?
+                .into_shape(),
+        };
+        
+        Ok(shape)
+    }
+    
+    fn from_shape(shape: &Shape) -> SceneShape {
+        match *shape {
+            Shape::Circle(ref circle) => SceneShape::Circle { radius: circle.radius },
+            Shape::Polygon(ref polygon) => SceneShape::Polygon {
+                vertices: polygon.vertices.clone(),
+                radius: polygon.radius,
+            },
+            Shape::Capsule(ref capsule) => SceneShape::Capsule {
+                half_length: capsule.half_length,
+                radius: capsule.radius,
+            },
+            Shape::Ellipse(ref ellipse) => SceneShape::Ellipse { half_extents: ellipse.half_extents },
+            Shape::Segment(ref segment) => SceneShape::Segment { a: segment.a, b: segment.b },
+            Shape::Chain(ref chain) => SceneShape::Chain {
+                vertices: chain.vertices().to_vec(),
+                closed: chain.is_closed(),
+            },
+            // The support mapping cannot be saved, so the shape is approximated by its outline
+            Shape::Convex(ref convex) => SceneShape::Polygon {
+                vertices: convex.outline().vertices.clone(),
+                radius: convex.outline().radius,
+            },
+            Shape::Heightfield(ref heightfield) => SceneShape::Heightfield {
+                heights: heightfield.heights().to_vec(),
+                scale: heightfield.scale(),
+            },
+        }
+    }
+}
+
+fn default_density() -> f32 {
+    1.0
+}
+
+fn is_zero(value: &f32) -> bool {
+    *value == 0.0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// A fixture of a body in a scene.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneFixture {
+    pub shape: SceneShape,
+    /// The position of the shape relative to the body.
+    #[serde(default)]
+    pub position: Vec2,
+    /// The rotation of the shape relative to the body.
+    #[serde(default)]
+    pub rotation: f32,
+    #[serde(default = "default_density")]
+    pub density: f32,
+    #[serde(default)]
+    pub material: Material,
+    #[serde(default)]
+    pub filter: Filter,
+}
+
+impl SceneFixture {
+    /// Builds the fixture of the body named `body_name`.
+    fn to_fixture(&self, body_name: &str) -> Result<Fixture, SceneError> {
+        let mut fixture = Fixture::new(self.shape.to_shape(body_name)?, self.density, self.material.clone());
+        fixture.transform = Transform::new(self.position, self.rotation);
+        fixture.filter = self.filter;
+        Ok(fixture)
+    }
+    
+    fn from_fixture(fixture: &Fixture) -> SceneFixture {
+        SceneFixture {
+            shape: SceneShape::from_shape(&fixture.shape),
+            position: fixture.transform.position,
+            rotation: fixture.transform.rotation(),
+            density: fixture.density,
+            material: fixture.material.clone(),
+            filter: fixture.filter,
+        }
+    }
+}
+
+/// A named body in a scene.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneBody {
+    /// The name used to reference this body from joints. Must be unique within the scene.
+    pub name: String,
+    /// The shape of a fixture at the body origin, with the body's `density` and `material`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<SceneShape>,
+    #[serde(default = "default_density")]
+    pub density: f32,
+    #[serde(default)]
+    pub material: Material,
+    /// Further fixtures of the body.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixtures: Vec<SceneFixture>,
+    #[serde(default, rename = "static")]
+    pub is_static: bool,
+    /// Overrides the mass computed from the fixtures.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mass: Option<f32>,
+    /// Overrides the rotational inertia about the center of mass computed from the fixtures.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inertia: Option<f32>,
+    /// Overrides the center of mass relative to the body origin computed from the fixtures.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_center: Option<Vec2>,
+    
+    #[serde(default)]
+    pub position: Vec2,
+    #[serde(default)]
+    pub rotation: f32,
+    #[serde(default)]
+    pub velocity: Vec2,
+    #[serde(default)]
+    pub angular_velocity: f32,
+    
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub linear_damping: f32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub angular_damping: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_linear_speed: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_angular_speed: Option<f32>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub fixed_rotation: bool,
+}
+
+impl SceneBody {
+    fn to_body(&self) -> Result<Body, SceneError> {
+        let fixtures = self.shape.iter()
+            .map(|shape| Ok(Fixture::new(shape.to_shape(&self.name)?, self.density, self.material.clone())))
+            .chain(self.fixtures.iter().map(|fixture| fixture.to_fixture(&self.name)))
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let mut body = Body::with_fixtures(fixtures);
+        
+        body.transform.position = self.position;
+        body.transform.set_rotation(self.rotation);
+        body.velocity = self.velocity;
+        body.angular_vel = self.angular_velocity;
+        body.linear_damping = self.linear_damping;
+        body.angular_damping = self.angular_damping;
+        body.max_linear_speed = self.max_linear_speed;
+        body.max_angular_speed = self.max_angular_speed;
+        body.set_fixed_rotation(self.fixed_rotation);
+        
+        if self.mass.is_some() || self.inertia.is_some() || self.local_center.is_some() {
+            let mass = self.mass.unwrap_or_else(|| body.mass());
+            let inertia = self.inertia.unwrap_or_else(|| body.inertia());
+            let local_center = self.local_center.unwrap_or_else(|| body.local_center());
+            
+            if !valid_mass_properties(mass, inertia, local_center) {
+                return Err(SceneError::InvalidMass(self.name.clone()));
+            }
+            
+            body.set_mass_properties(mass, inertia, local_center);
+        }
+        
+        if self.is_static {
+            body.set_static();
+        }
+        
+        Ok(body)
+    }
+    
+    fn from_body(name: String, body: &Body) -> SceneBody {
+        let fixtures = body.fixtures();
+        
+        // A single fixture at the body origin is written in the short form
+        let (shape, density, material, fixtures) = match fixtures {
+            [fixture] if fixture.transform.position == Vec2::ZERO && fixture.transform.rotation() == 0.0 &&
+                fixture.filter == Filter::default() => {
+                (Some(SceneShape::from_shape(&fixture.shape)), fixture.density, fixture.material.clone(), Vec::new())
+            },
+            _ => {
+                let fixtures = fixtures.iter().map(SceneFixture::from_fixture).collect();
+                (None, default_density(), Material::default(), fixtures)
+            },
+        };
+        
+        // Mass properties set with `Body::set_mass_properties` differ from those of the shapes
+        let mass_properties = (body.mass(), body.inertia(), body.local_center());
+        let (mass, inertia, local_center) = if !body.is_static() && mass_properties != body.mass_from_shape() {
+            (Some(mass_properties.0), Some(mass_properties.1), Some(mass_properties.2))
+        } else {
+            (None, None, None)
+        };
+        
+        SceneBody {
+            name,
+            shape,
+            density,
+            material,
+            fixtures,
+            is_static: body.is_static(),
+            mass,
+            inertia,
+            local_center,
+            position: body.transform.position,
+            rotation: body.transform.rotation(),
+            velocity: body.velocity,
+            angular_velocity: body.angular_vel,
+            linear_damping: body.linear_damping,
+            angular_damping: body.angular_damping,
+            max_linear_speed: body.max_linear_speed,
+            max_angular_speed: body.max_angular_speed,
+            fixed_rotation: body.fixed_rotation(),
+        }
+    }
+}
+
+/// The type and parameters of a joint in a scene.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SceneJointKind {
+    Spring {
+        #[serde(default)]
+        anchor_a: Vec2,
+        #[serde(default)]
+        anchor_b: Vec2,
+        /// The rest length of the spring. If omitted, the distance between the anchors when the
+        /// scene is loaded is used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distance: Option<f32>,
+        frequency: f32,
+        damping: f32,
+    },
+}
+
+/// A joint between two named bodies in a scene.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneJoint {
+    pub body_a: String,
+    pub body_b: String,
+    #[serde(flatten)]
+    pub kind: SceneJointKind,
+}
+
+impl SceneJoint {
+    fn to_joint(&self, body_a: &Body, body_b: &Body) -> Joint {
+        match self.kind {
+            SceneJointKind::Spring { anchor_a, anchor_b, distance, frequency, damping } => {
+                let distance = distance.unwrap_or_else(|| {
+                    (body_b.transform.world_pos(&anchor_b) - body_a.transform.world_pos(&anchor_a)).len()
+                });
+                
+                SpringJoint::new(anchor_a, anchor_b, distance, frequency, damping).into_joint()
+            }
+        }
+    }
+    
+    fn from_joint(body_a: String, body_b: String, joint: &Joint) -> SceneJoint {
+        let kind = match *joint {
+            Joint::Spring(ref spring) => SceneJointKind::Spring {
+                anchor_a: spring.local_anchor_a,
+                anchor_b: spring.local_anchor_b,
+                distance: Some(spring.distance),
+                frequency: spring.frequency,
+                damping: spring.damping,
+            },
+        };
+        
+        SceneJoint {
+            body_a,
+            body_b,
+            kind,
+        }
+    }
+}
+
+/// The solver settings of a scene.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SceneSolver {
+    pub velocity_iterations: u8,
+    pub position_iterations: u8,
+    pub deterministic: bool,
+}
+
+impl Default for SceneSolver {
+    fn default() -> SceneSolver {
+        SceneSolver {
+            velocity_iterations: World::DEFAULT_VELOCITY_ITERATIONS,
+            position_iterations: World::DEFAULT_POSITION_ITERATIONS,
+            deterministic: false,
+        }
+    }
+}
+
+/// An override of the friction and restitution between two material ids in a scene.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneMaterialPair {
+    pub material_a: MaterialId,
+    pub material_b: MaterialId,
+    #[serde(flatten)]
+    pub pair: MaterialPair,
+}
+
+fn default_gravity() -> Vec2 {
+    World::DEFAULT_GRAVITY
+}
+
+/// A scene: a data description of a `World`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Scene {
+    /// The version of the scene format. Must be `Scene::VERSION`.
+    pub version: u32,
+    #[serde(default = "default_gravity")]
+    pub gravity: Vec2,
+    #[serde(default)]
+    pub solver: SceneSolver,
+    pub bodies: Vec<SceneBody>,
+    #[serde(default)]
+    pub joints: Vec<SceneJoint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub material_pairs: Vec<SceneMaterialPair>,
+}
+
+impl Scene {
+    /// The current version of the scene format.
+    pub const VERSION: u32 = 1;
+    
+    /// Reads a scene in JSON form from `reader`.
+    pub fn load<R: io::Read>(reader: R) -> Result<Scene, SceneError> {
+        let scene: Scene = serde_json::from_reader(reader)?;
+        
+        if scene.version != Scene::VERSION {
+            return Err(SceneError::UnsupportedVersion(scene.version));
+        }
+        
+        Ok(scene)
+    }
+    
+    /// Writes this scene in JSON form to `writer`.
+    pub fn save<W: io::Write>(&self, writer: W) -> Result<(), SceneError> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+    
+    /// Creates a scene describing `world`. Bodies are named `body_<id>` after their `BodyId`.
+    pub fn from_world(world: &World) -> Scene {
+        let body_name = |body_id: BodyId| format!("body_{}", body_id);
+        
+        let bodies = world.bodies
+            .iter()
+            .map(|body| SceneBody::from_body(body_name(body.id), body))
+            .collect();
+        
+        let mut body_pairs: Vec<_> = world.joints.keys().collect();
+        body_pairs.sort_unstable();
+        
+        let joints = body_pairs
+            .into_iter()
+            .flat_map(|pair| {
+                world.joints[pair].iter().map(move |joint| {
+                    SceneJoint::from_joint(body_name(pair.0), body_name(pair.1), joint)
+                })
+            })
+            .collect();
+        
+        let material_pairs = world.material_pairs
+            .sorted()
+            .into_iter()
+            .map(|(material_a, material_b, pair)| SceneMaterialPair { material_a, material_b, pair })
+            .collect();
+        
+        Scene {
+            version: Scene::VERSION,
+            gravity: world.gravity,
+            solver: SceneSolver {
+                velocity_iterations: world.velocity_iterations,
+                position_iterations: world.position_iterations,
+                deterministic: world.deterministic,
+            },
+            bodies,
+            joints,
+            material_pairs,
+        }
+    }
+    
+    /// Adds the bodies and joints of this scene to `world` and applies its gravity and solver
+    /// settings.
+    ///
+    /// Returns the `BodyId` of each body, keyed by its name. The world is left unchanged if an
+    /// error is returned.
+    pub fn add_to_world(&self, world: &mut World) -> Result<HashMap<String, BodyId>, SceneError> {
+        let mut bodies: HashMap<&str, Body> = HashMap::with_capacity(self.bodies.len());
+        
+        for scene_body in self.bodies.iter() {
+            if bodies.insert(&scene_body.name, scene_body.to_body()?).is_some() {
+                return Err(SceneError::DuplicateBodyName(scene_body.name.clone()));
+            }
+        }
+        
+        let mut joints = Vec::with_capacity(self.joints.len());
+        
+        for scene_joint in self.joints.iter() {
+            let body_a = bodies.get(scene_joint.body_a.as_str())
+                               .ok_or_else(|| SceneError::UnknownBody(scene_joint.body_a.clone()))?;
+            let body_b = bodies.get(scene_joint.body_b.as_str())
+                               .ok_or_else(|| SceneError::UnknownBody(scene_joint.body_b.clone()))?;
+            
+            joints.push(scene_joint.to_joint(body_a, body_b));
+        }
+        
+        world.gravity = self.gravity;
+        world.velocity_iterations = self.solver.velocity_iterations;
+        world.position_iterations = self.solver.position_iterations;
+        world.deterministic = self.solver.deterministic;
+        
+        for material_pair in self.material_pairs.iter() {
+            world.set_material_pair(material_pair.material_a, material_pair.material_b, material_pair.pair);
+        }
+        
+        // Add bodies in scene order so that body ids follow the order of the file
+        let mut body_ids = HashMap::with_capacity(self.bodies.len());
+        
+        for scene_body in self.bodies.iter() {
+            let body = bodies.remove(scene_body.name.as_str()).unwrap();
+            body_ids.insert(scene_body.name.clone(), world.add_body(body));
+        }
+        
+        for (scene_joint, joint) in self.joints.iter().zip(joints) {
+            world.add_joint((body_ids[&scene_joint.body_a], body_ids[&scene_joint.body_b]), joint);
+        }
+        
+        Ok(body_ids)
+    }
+}
+
+impl World {
+    /// Loads a JSON scene from `reader` into this world, adding its bodies and joints and
+    /// applying its gravity and solver settings.
+    ///
+    /// Returns the `BodyId` of each body in the scene, keyed by its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physics2d::World;
+    /// let json = r#"{
+    ///     "version": 1,
+    ///     "bodies": [{ "name": "ball", "shape": { "type": "circle", "radius": 1.0 } }]
+    /// }"#;
+    ///
+    /// let mut world = World::default();
+    /// let bodies = world.load_scene(json.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(world.get_body(bodies["ball"]).transform.position.y, 0.0);
+    /// ```
+    pub fn load_scene<R: io::Read>(&mut self, reader: R) -> Result<HashMap<String, BodyId>, SceneError> {
+        Scene::load(reader)?.add_to_world(self)
+    }
+    
+    /// Saves this world as a JSON scene to `writer`. Bodies are named `body_<id>` after their
+    /// `BodyId`.
+    pub fn save_scene<W: io::Write>(&self, writer: W) -> Result<(), SceneError> {
+        Scene::from_world(self).save(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SCENE: &str = r#"{
+        "version": 1,
+        "gravity": { "x": 0.0, "y": -20.0 },
+        "solver": { "velocity_iterations": 12 },
+        "bodies": [
+            {
+                "name": "ground",
+                "shape": { "type": "polygon", "vertices": [
+                    { "x": -10.0, "y": -0.5 }, { "x": 10.0, "y": -0.5 },
+                    { "x": 10.0, "y": 0.5 }, { "x": -10.0, "y": 0.5 }
+                ] },
+                "static": true
+            },
+            {
+                "name": "ball",
+                "shape": { "type": "circle", "radius": 1.0 },
+                "density": 10.0,
+                "position": { "x": 0.0, "y": 5.0 }
+            }
+        ],
+        "joints": [
+            { "type": "spring", "body_a": "ground", "body_b": "ball", "frequency": 1.5, "damping": 0.7 }
+        ]
+    }"#;
+    
+    #[test]
+    fn load() {
+        let mut world = World::default();
+        let bodies = world.load_scene(SCENE.as_bytes()).unwrap();
+        
+        assert_eq!(world.body_count(), 2);
+        assert_eq!(world.gravity, Vec2::new(0.0, -20.0));
+        assert_eq!(world.velocity_iterations, 12);
+        assert_eq!(world.position_iterations, World::default().position_iterations);
+        
+        assert!(world.get_body(bodies["ground"]).is_static());
+        assert_eq!(world.get_body(bodies["ball"]).transform.position, Vec2::new(0.0, 5.0));
+        
+        let joints = world.get_joints((bodies["ground"], bodies["ball"])).unwrap();
+        let Joint::Spring(ref spring) = joints[0];
+        assert_eq!(spring.distance, 5.0);
+    }
+    
+    #[test]
+    fn save_and_reload() {
+        let mut world = World::default();
+        world.load_scene(SCENE.as_bytes()).unwrap();
+        world.set_material_pair(2, 1, MaterialPair::new(0.05, 0.3));
+        world.get_body_mut(1).linear_damping = 0.5;
+        world.get_body_mut(1).max_angular_speed = Some(10.0);
+        world.get_body_mut(1).set_fixed_rotation(true);
+        
+        let mut json = Vec::new();
+        world.save_scene(&mut json).unwrap();
+        
+        let mut reloaded = World::default();
+        let bodies = reloaded.load_scene(json.as_slice()).unwrap();
+        
+        assert_eq!(reloaded.body_count(), 2);
+        assert_eq!(reloaded.gravity, world.gravity);
+        assert_eq!(reloaded.get_material_pair(1, 2), Some(&MaterialPair::new(0.05, 0.3)));
+        
+        let ball = reloaded.get_body(bodies["body_1"]);
+        assert_eq!(ball.mass, world.get_body(1).mass);
+        assert_eq!(ball.linear_damping, 0.5);
+        assert_eq!(ball.max_angular_speed, Some(10.0));
+        assert!(ball.fixed_rotation());
+        assert!(reloaded.get_joints((bodies["body_0"], bodies["body_1"])).is_some());
+    }
+    
+    #[test]
+    fn mass_overrides() {
+        let mut world = World::default();
+        world.load_scene(SCENE.as_bytes()).unwrap();
+        world.get_body_mut(1).set_mass_properties(2.0, 3.0, Vec2::new(0.25, 0.0));
+        
+        let mut json = Vec::new();
+        world.save_scene(&mut json).unwrap();
+        
+        // Bodies whose mass comes from their shapes do not store it
+        let scene = Scene::load(json.as_slice()).unwrap();
+        assert_eq!(scene.bodies[0].mass, None);
+        assert_eq!(scene.bodies[1].mass, Some(2.0));
+        
+        let mut reloaded = World::default();
+        let bodies = reloaded.load_scene(json.as_slice()).unwrap();
+        
+        let ball = reloaded.get_body(bodies["body_1"]);
+        assert_eq!(ball.mass(), 2.0);
+        assert_eq!(ball.inertia(), 3.0);
+        assert_eq!(ball.local_center(), Vec2::new(0.25, 0.0));
+        
+        // Omitted overrides come from the shapes
+        let json = r#"{ "version": 1, "bodies": [{
+            "name": "ball", "shape": { "type": "circle", "radius": 1.0 }, "mass": 5.0
+        }] }"#;
+        
+        let bodies = reloaded.load_scene(json.as_bytes()).unwrap();
+        let ball = reloaded.get_body(bodies["ball"]);
+        assert_eq!(ball.mass(), 5.0);
+        assert_eq!(ball.local_center(), Vec2::ZERO);
+        
+        let json = json.replace("5.0", "-1.0");
+        match World::default().load_scene(json.as_bytes()) {
+            Err(SceneError::InvalidMass(name)) => assert_eq!(name, "ball"),
+            _ => panic!("expected an invalid mass error"),
+        }
+    }
+    
+    #[test]
+    fn fixtures() {
+        let json = r#"{
+            "version": 1,
+            "bodies": [{
+                "name": "hammer",
+                "fixtures": [
+                    { "shape": { "type": "capsule", "half_length": 1.0, "radius": 0.1 } },
+                    { "shape": { "type": "circle", "radius": 0.3 }, "position": { "x": 1.0, "y": 0.0 },
+                      "density": 5.0, "filter": { "category_bits": 2, "mask_bits": 65535, "group_index": 0 } }
+                ]
+            }]
+        }"#;
+        
+        let mut world = World::default();
+        let bodies = world.load_scene(json.as_bytes()).unwrap();
+        
+        let check_hammer = |body: &Body| {
+            let fixtures = body.fixtures();
+            
+            assert_eq!(fixtures.len(), 2);
+            assert_eq!(fixtures[1].transform.position, Vec2::new(1.0, 0.0));
+            assert_eq!(fixtures[1].density, 5.0);
+            assert_eq!(fixtures[1].filter, Filter::new(2, 0xffff, 0));
+        };
+        
+        check_hammer(world.get_body(bodies["hammer"]));
+        
+        let mut json = Vec::new();
+        world.save_scene(&mut json).unwrap();
+        
+        let mut reloaded = World::default();
+        let bodies = reloaded.load_scene(json.as_slice()).unwrap();
+        
+        let hammer = reloaded.get_body(bodies["body_0"]);
+        check_hammer(hammer);
+        assert_eq!(hammer.mass, world.get_body(0).mass);
+    }
+    
+    #[test]
+    fn example_scenes() {
+        let scenes = [
+            include_str!("../../examples/scenes/pyramid.json"),
+            include_str!("../../examples/scenes/stacks.json"),
+            include_str!("../../examples/scenes/springs.json"),
+        ];
+        
+        for scene in scenes.iter() {
+            World::default().load_scene(scene.as_bytes()).unwrap();
+        }
+    }
+    
+    #[test]
+    fn errors() {
+        let unsupported = r#"{ "version": 999, "bodies": [] }"#;
+        
+        match World::default().load_scene(unsupported.as_bytes()) {
+            Err(SceneError::UnsupportedVersion(999)) => {}
+            _ => panic!("expected an unsupported version error"),
+        }
+        
+        let unknown_body = SCENE.replace(r#""body_b": "ball""#, r#""body_b": "missing""#);
+        let mut world = World::default();
+        
+        match world.load_scene(unknown_body.as_bytes()) {
+            Err(SceneError::UnknownBody(ref name)) if name == "missing" => {}
+            _ => panic!("expected an unknown body error"),
+        }
+        
+        assert_eq!(world.body_count(), 0);
+        
+        // Swap the second and fourth vertices of the ground
+        let clockwise = SCENE.replace(r#"{ "x": 10.0, "y": -0.5 }"#, "FOURTH")
+                             .replace(r#"{ "x": -10.0, "y": 0.5 }"#, r#"{ "x": 10.0, "y": -0.5 }"#)
+                             .replace("FOURTH", r#"{ "x": -10.0, "y": 0.5 }"#);
+        
+        match world.load_scene(clockwise.as_bytes()) {
+            Err(SceneError::InvalidPolygon(ref name, PolygonError::Clockwise)) if name == "ground" => {}
+            _ => panic!("expected an invalid polygon error"),
+        }
+        
+        let short_chain = r#"{
+            "version": 1,
+            "bodies": [{ "name": "ledge", "shape": { "type": "chain", "vertices": [{ "x": 0.0, "y": 0.0 }] } }]
+        }"#;
+        
+        match world.load_scene(short_chain.as_bytes()) {
+            Err(SceneError::InvalidChain(ref name)) if name == "ledge" => {}
+            _ => panic!("expected an invalid chain error"),
+        }
+        
+        let flat_terrain = r#"{
+            "version": 1,
+            "bodies": [{ "name": "terrain",
+                         "shape": { "type": "heightfield", "heights": [0.0], "scale": { "x": 1.0, "y": 1.0 } } }]
+        }"#;
+        
+        match world.load_scene(flat_terrain.as_bytes()) {
+            Err(SceneError::InvalidHeightfield(ref name, HeightfieldError::TooFewHeights)) if name == "terrain" => {}
+            _ => panic!("expected an invalid heightfield error"),
+        }
+        
+        assert_eq!(world.body_count(), 0);
+    }
+}
@@ -0,0 +1,249 @@
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+use fnv::FnvHashMap;
+
+use crate::world::{World, Body, BodyId, BodyPair, MaterialId, MaterialPair};
+use crate::joint::Joint;
+use crate::math::Vec2;
+
+/// A joint between two bodies, as stored in a serialized `World`.
+#[derive(Serialize, Deserialize)]
+struct SerializedJoint<J> {
+    body_a: BodyId,
+    body_b: BodyId,
+    joint: J,
+}
+
+/// An override of the friction and restitution between two material ids, as stored in a
+/// serialized `World`.
+#[derive(Serialize, Deserialize)]
+struct SerializedMaterialPair {
+    material_a: MaterialId,
+    material_b: MaterialId,
+    #[serde(flatten)]
+    pair: MaterialPair,
+}
+
+/// The serialized form of a `World`, borrowing from the world being serialized.
+#[derive(Serialize)]
+struct SerializedWorldRef<'a> {
+    bodies: Vec<&'a Body>,
+    joints: Vec<SerializedJoint<&'a Joint>>,
+    material_pairs: Vec<SerializedMaterialPair>,
+    
+    gravity: Vec2,
+    velocity_iterations: u8,
+    position_iterations: u8,
+    deterministic: bool,
+}
+
+/// The serialized form of a `World`.
+#[derive(Deserialize)]
+struct SerializedWorld {
+    bodies: Vec<Body>,
+    joints: Vec<SerializedJoint<Joint>>,
+    /// Missing from worlds serialized before material pairs were.
+    #[serde(default)]
+    material_pairs: Vec<SerializedMaterialPair>,
+    
+    gravity: Vec2,
+    velocity_iterations: u8,
+    position_iterations: u8,
+    deterministic: bool,
+}
+
+/// A `World` is serialized as its bodies, joints, material pairs and solver settings.
+///
+/// Contacts are not serialized; they are recreated by the first step after deserialization,
+/// without the accumulated impulses used for warm starting. Use `World::snapshot` to capture
+/// those in memory.
+impl Serialize for World {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut body_pairs: Vec<&BodyPair> = self.joints.keys().collect();
+        body_pairs.sort_unstable();
+        
+        let joints = body_pairs.into_iter()
+            .flat_map(|pair| {
+                self.joints[pair].iter().map(move |joint| SerializedJoint {
+                    body_a: pair.0,
+                    body_b: pair.1,
+                    joint,
+                })
+            })
+            .collect();
+        
+        let material_pairs = self.material_pairs.sorted().into_iter()
+            .map(|(material_a, material_b, pair)| SerializedMaterialPair { material_a, material_b, pair })
+            .collect();
+        
+        SerializedWorldRef {
+            bodies: self.bodies.iter().collect(),
+            joints,
+            material_pairs,
+            gravity: self.gravity,
+            velocity_iterations: self.velocity_iterations,
+            position_iterations: self.position_iterations,
+            deterministic: self.deterministic,
+        }.serialize(serializer)
+    }
+}
+
+/// Bodies may be assigned new `BodyId`s when deserialized; joints are remapped accordingly.
+impl<'de> Deserialize<'de> for World {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<World, D::Error> {
+        let serialized = SerializedWorld::deserialize(deserializer)?;
+        
+        let mut world = World::new(serialized.velocity_iterations, serialized.position_iterations);
+        world.gravity = serialized.gravity;
+        world.deterministic = serialized.deterministic;
+        
+        let mut body_ids = FnvHashMap::default();
+        
+        for body in serialized.bodies {
+            let serialized_id = body.id;
+            body_ids.insert(serialized_id, world.add_body(body));
+        }
+        
+        for SerializedJoint { body_a, body_b, joint } in serialized.joints {
+            let body_a = *body_ids.get(&body_a)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid joint body id {}", body_a)))?;
+            let body_b = *body_ids.get(&body_b)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid joint body id {}", body_b)))?;
+            
+            world.add_joint((body_a, body_b), joint);
+        }
+        
+        for SerializedMaterialPair { material_a, material_b, pair } in serialized.material_pairs {
+            world.set_material_pair(material_a, material_b, pair);
+        }
+        
+        Ok(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{self, Vec2};
+    use crate::shapes::{Shape, Circle, Polygon};
+    use crate::world::{Material, MaterialPair, Transform};
+    use crate::joint::SpringJoint;
+    
+    fn round_trip<T: Serialize + for<'de> Deserialize<'de>>(value: &T) -> T {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+    
+    #[test]
+    fn transform_rebuilds_rotation_matrix() {
+        let transform = Transform::new(Vec2::new(1.0, 2.0), math::PI / 2.0);
+        
+        let json = serde_json::to_value(transform).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 2);
+        
+        let loaded: Transform = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.position, transform.position);
+        assert_eq!(loaded.rotation(), transform.rotation());
+        assert_eq!(loaded.world_dir(&Vec2::RIGHT), transform.world_dir(&Vec2::RIGHT));
+    }
+    
+    #[test]
+    fn shapes() {
+        match round_trip(&Circle::new(0.5).into_shape()) {
+            Shape::Circle(circle) => assert_eq!(circle.radius, 0.5),
+            _ => panic!("expected a circle"),
+        }
+        
+        let polygon = Polygon::new_rounded(Polygon::rect(2.0, 1.0).vertices, 0.1);
+        let loaded = round_trip(&polygon);
+        
+        assert_eq!(loaded.vertices, polygon.vertices);
+        assert_eq!(loaded.normals, polygon.normals);
+        assert_eq!(loaded.radius, 0.1);
+        
+        let json = r#"{ "Heightfield": { "heights": [0.0, 1.0], "scale": { "x": -1.0, "y": 1.0 } } }"#;
+        assert!(serde_json::from_str::<Shape>(json).is_err());
+    }
+    
+    #[test]
+    fn body() {
+        let mut body = Body::new(Polygon::rect(2.0, 1.0).into_shape(), 3.0, Material::new(0.4, 0.2));
+        body.transform = Transform::new(Vec2::new(1.0, -1.0), 0.5);
+        body.velocity = Vec2::new(2.0, 3.0);
+        body.angular_vel = -1.0;
+        
+        let loaded = round_trip(&body);
+        
+        assert_eq!(loaded.transform.position, body.transform.position);
+        assert_eq!(loaded.transform.rotation(), 0.5);
+        assert_eq!(loaded.velocity, body.velocity);
+        assert_eq!(loaded.angular_vel, -1.0);
+        assert_eq!(loaded.mass(), body.mass());
+        assert_eq!(loaded.inertia(), body.inertia());
+        assert_eq!(loaded.fixtures().len(), 1);
+        assert_eq!(loaded.fixtures()[0].material.friction, 0.4);
+    }
+    
+    /// Returns a serialized world of three circles with a spring between the first and the last.
+    fn world_json() -> serde_json::Value {
+        let mut world = World::default();
+        
+        let bodies: Vec<BodyId> = (0..3)
+            .map(|_| world.add_body(Body::new(Circle::new(0.5).into_shape(), 1.0, Material::default())))
+            .collect();
+        world.add_joint((bodies[0], bodies[2]), SpringJoint::new(Vec2::ZERO, Vec2::ZERO, 1.0, 2.0, 0.3).into_joint());
+        
+        serde_json::to_value(world).unwrap()
+    }
+    
+    #[test]
+    fn world_remaps_joint_body_ids() {
+        let mut json = world_json();
+        
+        // Ids that are not those the bodies are given when added to a new world
+        for (body, id) in json["bodies"].as_array_mut().unwrap().iter_mut().zip(&[10, 20, 30]) {
+            body["id"] = (*id).into();
+        }
+        
+        json["joints"][0]["body_a"] = 10.into();
+        json["joints"][0]["body_b"] = 30.into();
+        
+        let world: World = serde_json::from_value(json).unwrap();
+        
+        assert_eq!(world.body_count(), 3);
+        assert_eq!(world.get_joints((0, 2)).map(Vec::len), Some(1));
+    }
+    
+    #[test]
+    fn world_invalid_joint_body_id() {
+        let mut json = world_json();
+        json["joints"][0]["body_b"] = 99.into();
+        
+        match serde_json::from_value::<World>(json) {
+            Err(error) => assert!(error.to_string().contains("invalid joint body id 99")),
+            Ok(_) => panic!("expected an invalid joint body id error"),
+        }
+    }
+    
+    #[test]
+    fn world_material_pairs() {
+        let mut world = World::default();
+        world.set_material_pair(3, 3, MaterialPair::new(0.9, 0.0));
+        world.set_material_pair(2, 1, MaterialPair::new(0.05, 0.3));
+        
+        // Stored in ascending order of their material ids
+        let json = serde_json::to_value(&world).unwrap();
+        assert_eq!(json["material_pairs"][0]["material_a"], 1);
+        assert_eq!(json["material_pairs"][0]["material_b"], 2);
+        
+        let loaded: World = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.get_material_pair(1, 2), Some(&MaterialPair::new(0.05, 0.3)));
+        assert_eq!(loaded.get_material_pair(3, 3), Some(&MaterialPair::new(0.9, 0.0)));
+        
+        // Worlds serialized without material pairs still load
+        let mut json = world_json();
+        json.as_object_mut().unwrap().remove("material_pairs");
+        
+        let loaded: World = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.get_material_pair(1, 2), None);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::world::{World, BodyId, ConstraintsMap};
+use crate::world::body::BodyState;
+use crate::collision::ContactManifold;
+use crate::collision::broad_phase::BoundsTreeBroadPhase;
+use crate::joint::Joint;
+
+/// The simulation state of a `World` at some point in time.
+///
+/// A snapshot holds the transforms, velocities and pending forces of every body, the accumulated
+/// contact and joint impulses used for warm starting and the state of the broad phase. Restoring
+/// it with `World::restore` makes the following steps produce exactly the same results as the
+/// steps that followed `World::snapshot`, which is what rollback networking relies on.
+///
+/// Shapes, materials and other body properties that are not changed by a step are not stored.
+/// The broad phase tree and the contact and joint maps are copied whole though, so taking a
+/// snapshot allocates and costs time proportional to the number of bodies, contacts and joints.
+/// Keeping one snapshot per frame of a rollback window is fine for small worlds, but large worlds
+/// may want to take them less often.
+#[derive(Clone)]
+pub struct WorldSnapshot {
+    bodies: Vec<(BodyId, BodyState)>,
+    
+    broad_phase: BoundsTreeBroadPhase,
+    
+    contact_constraints: ConstraintsMap<ContactManifold>,
+    joints: ConstraintsMap<Joint>,
+}
+
+impl World {
+    /// Captures the current simulation state of the world.
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            bodies: self.bodies.iter().map(|body| (body.id, body.state())).collect(),
+            broad_phase: self.broad_phase.clone(),
+            contact_constraints: self.contact_constraints.clone(),
+            joints: self.joints.clone(),
+        }
+    }
+    
+    /// Restores the simulation state captured in `snapshot`.
+    ///
+    /// Joints added since the snapshot was taken are removed, and joint parameters changed since
+    /// are reverted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if bodies were added to or removed from the world after the snapshot was taken.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        assert_eq!(self.bodies.len(), snapshot.bodies.len(),
+                   "Bodies were added to or removed from the world after the snapshot was taken");
+        
+        for (body_id, state) in snapshot.bodies.iter() {
+            self.bodies[*body_id].set_state(state);
+        }
+        
+        self.broad_phase.clone_from(&snapshot.broad_phase);
+        self.contact_constraints.clone_from(&snapshot.contact_constraints);
+        self.joints.clone_from(&snapshot.joints);
+    }
+}
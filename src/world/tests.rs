@@ -0,0 +1,932 @@
+use super::*;
+use crate::math::{self, Vec2};
+use crate::shapes::{Shape, Circle, Polygon, Capsule, Ellipse, Segment, Chain, Heightfield, Convex, Support};
+use crate::debug::DebugCollision;
+use crate::joint::SpringJoint;
+use crate::collision::Contact;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Returns the contacts between bodies `a` and `b`.
+fn contacts(world: &World, a: BodyId, b: BodyId) -> Vec<Contact> {
+    world.contact_constraints[&BodyPair::new(a, b)].iter().flat_map(|manifold| manifold.contacts().cloned()).collect()
+}
+
+/// Builds a small scene with a stack of boxes and a chain of circles linked by springs. The springs
+/// are added in reverse order if `reverse_joints` is `true`.
+fn build_world(reverse_joints: bool) -> World {
+    let mut world = World {
+        deterministic: true,
+        ..World::default()
+    };
+    
+    let mut ground = Body::new(Polygon::rect(40.0, 1.0).into_shape(), 10.0, Material::new(0.4, 0.4));
+    ground.set_static();
+    world.add_body(ground);
+    
+    for i in 0..6 {
+        let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, Material::new(0.3, 0.3));
+        body.transform.position = Vec2::new(0.1 * i as f32, 1.0 + 1.05 * i as f32);
+        world.add_body(body);
+    }
+    
+    let mut circles = Vec::new();
+    
+    for i in 0..5 {
+        let mut body = Body::new(Circle::new(0.5).into_shape(), 10.0, Material::new(0.3, 0.3));
+        body.transform.position = Vec2::new(5.0 + 1.2 * i as f32, 3.0 + 0.3 * i as f32);
+        circles.push(world.add_body(body));
+    }
+    
+    let mut links: Vec<(BodyId, BodyId)> = circles.windows(2).map(|ids| (ids[0], ids[1])).collect();
+    
+    if reverse_joints {
+        links.reverse();
+    }
+    
+    for (a, b) in links {
+        world.add_joint((a, b), SpringJoint::new(Vec2::ZERO, Vec2::ZERO, 1.0, 2.0, 0.3).into_joint());
+    }
+    
+    world
+}
+
+fn transform_bits(world: &World) -> Vec<(u32, u32, u32)> {
+    world.bodies_iter()
+         .map(|body| {
+             let position = body.transform.position;
+             (position.x.to_bits(), position.y.to_bits(), body.transform.rotation().to_bits())
+         })
+         .collect()
+}
+
+/// Steps two copies of the scene whose constraint maps have different histories, and returns
+/// whether their transforms stay bit-identical.
+fn same_transforms_with_different_map_histories(deterministic: bool) -> bool {
+    let mut world_a = build_world(false);
+    let mut world_b = build_world(true);
+    
+    world_a.deterministic = deterministic;
+    world_b.deterministic = deterministic;
+    
+    // Growing the maps rehashes them, which changes the order they iterate in
+    world_b.joints.reserve(1024);
+    world_b.contact_constraints.reserve(1024);
+    
+    for _ in 0..180 {
+        world_a.update(DT);
+        world_b.update(DT);
+        
+        if transform_bits(&world_a) != transform_bits(&world_b) {
+            return false;
+        }
+    }
+    
+    true
+}
+
+#[test]
+fn deterministic_independent_of_map_history() {
+    assert!(same_transforms_with_different_map_histories(true));
+}
+
+#[test]
+fn map_history_changes_result_when_not_deterministic() {
+    // Otherwise the test above would pass without solving in a fixed order
+    assert!(!same_transforms_with_different_map_histories(false));
+}
+
+#[test]
+fn restore_snapshot() {
+    const ROLLBACK_FRAMES: usize = 8;
+    
+    let mut world = build_world(false);
+    
+    for _ in 0..60 {
+        world.update(DT);
+    }
+    
+    let snapshot = world.snapshot();
+    
+    let mut expected = Vec::new();
+    
+    for _ in 0..ROLLBACK_FRAMES {
+        world.update(DT);
+        expected.push(transform_bits(&world));
+    }
+    
+    world.restore(&snapshot);
+    
+    for expected_bits in expected.iter() {
+        world.update(DT);
+        assert_eq!(&transform_bits(&world), expected_bits);
+    }
+}
+
+/// Drops `body` onto a static ground box whose top is at y = 0 and steps the world until it settles.
+fn settle_on_ground(body: Body) -> World {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(40.0, 1.0).into_shape(), 10.0, Material::new(0.4, 0.0));
+    ground.transform.position = Vec2::DOWN * 0.5;
+    ground.set_static();
+    world.add_body(ground);
+    
+    world.add_body(body);
+    
+    for _ in 0..240 {
+        world.update(DT);
+    }
+    
+    world
+}
+
+#[test]
+fn capsule_rests_on_polygon() {
+    let mut capsule = Body::new(Capsule::new(1.0, 0.5).into_shape(), 10.0, Material::new(0.4, 0.0));
+    capsule.transform.position = Vec2::UP * 2.0;
+    
+    let world = settle_on_ground(capsule);
+    let capsule = world.bodies_iter().find(|body| !body.is_static()).unwrap();
+    
+    assert!((capsule.transform.position.y - 0.5).abs() < 0.02);
+    assert!(capsule.transform.rotation().abs() < 0.01);
+    assert_eq!(world.contacts().len(), 2);
+}
+
+#[test]
+fn capsules_collide() {
+    let mut world = World {
+        gravity: Vec2::ZERO,
+        ..World::default()
+    };
+    
+    let mut lower = Body::new(Capsule::new(2.0, 0.5).into_shape(), 10.0, Material::new(0.4, 0.0));
+    lower.set_static();
+    let lower = world.add_body(lower);
+    
+    let mut upper = Body::new(Capsule::new(1.0, 0.5).into_shape(), 10.0, Material::new(0.4, 0.0));
+    upper.transform.position = Vec2::UP * 0.9;
+    let upper = world.add_body(upper);
+    
+    let mut circle = Body::new(Circle::new(0.5).into_shape(), 10.0, Material::new(0.4, 0.0));
+    circle.transform.position = Vec2::new(2.5, 0.5);
+    let circle = world.add_body(circle);
+    
+    world.update(DT);
+    
+    let contacts = contacts(&world, lower, upper);
+    assert_eq!(contacts.len(), 2);
+    
+    for contact in contacts.iter() {
+        assert!((contact.normal - Vec2::UP).len() < 1e-4);
+        assert!((contact.penetration - 0.1).abs() < 1e-4);
+    }
+    
+    let contacts = self::contacts(&world, lower, circle);
+    assert_eq!(contacts.len(), 1);
+    assert!(contacts[0].penetration > 0.0);
+}
+
+#[test]
+fn bodies_rest_on_segment() {
+    for shape in [Circle::new(0.5).into_shape(), Polygon::rect(1.0, 1.0).into_shape()].iter() {
+        let mut world = World::default();
+        
+        let segment = Body::new(Segment::new(Vec2::LEFT * 10.0, Vec2::RIGHT * 10.0).into_shape(), 0.0,
+                                Material::new(0.4, 0.0));
+        assert!(segment.is_static());
+        world.add_body(segment);
+        
+        let mut body = Body::new(shape.clone(), 10.0, Material::new(0.4, 0.0));
+        body.transform.position = Vec2::UP * 2.0;
+        let body = world.add_body(body);
+        
+        for _ in 0..240 {
+            world.update(DT);
+        }
+        
+        let body = world.get_body(body);
+        
+        assert!((body.transform.position.y - 0.5).abs() < 0.02);
+        assert!(body.velocity.len() < 0.01);
+    }
+}
+
+#[test]
+fn box_slides_across_chain_seams() {
+    for &speed in [0.2, 1.0, 5.0].iter() {
+        let mut world = World::default();
+        
+        let vertices = (0..=80).map(|i| Vec2::new(-5.0 + 0.5 * i as f32, 0.0)).collect();
+        let mut ground = Body::new(Chain::new(vertices).into_shape(), 0.0, Material::new(0.0, 0.0));
+        ground.set_static();
+        world.add_body(ground);
+        
+        let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, Material::new(0.0, 0.0));
+        body.transform.position = Vec2::new(-4.0, 0.49);
+        body.velocity = Vec2::RIGHT * speed;
+        let body = world.add_body(body);
+        
+        for _ in 0..240 {
+            world.update(DT);
+            
+            // Without ghost vertices, the box's corners catch on the seams and it bumps or stops
+            let body = world.get_body(body);
+            assert!((body.velocity.x - speed).abs() < 0.005);
+            assert!(body.transform.rotation().abs() < 0.005);
+            assert!((body.transform.position.y - 0.5).abs() < 0.02);
+        }
+    }
+}
+
+#[test]
+fn box_slides_across_heightfield_seams() {
+    for &speed in [0.2, 1.0, 5.0].iter() {
+        let mut world = World::default();
+        
+        let heightfield = Heightfield::new(vec![1.0; 81], Vec2::new(0.5, 0.5));
+        let mut ground = Body::new(heightfield.into_shape(), 0.0, Material::new(0.0, 0.0));
+        ground.transform.position = Vec2::new(-5.0, -0.5);
+        ground.set_static();
+        world.add_body(ground);
+        
+        let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, Material::new(0.0, 0.0));
+        body.transform.position = Vec2::new(-4.0, 0.49);
+        body.velocity = Vec2::RIGHT * speed;
+        let body = world.add_body(body);
+        
+        for _ in 0..240 {
+            world.update(DT);
+            
+            let body = world.get_body(body);
+            assert!((body.velocity.x - speed).abs() < 0.005);
+            assert!(body.transform.rotation().abs() < 0.005);
+            assert!((body.transform.position.y - 0.5).abs() < 0.02);
+        }
+    }
+}
+
+#[test]
+fn ball_rolls_in_heightfield_valley() {
+    let mut world = World::default();
+    
+    // A parabolic valley, y = 0.04 * x^2
+    let heights = (0..=20).map(|i| ((i as f32 - 10.0) * 0.1).powi(2)).collect();
+    let mut ground = Body::new(Heightfield::new(heights, Vec2::new(1.0, 4.0)).into_shape(), 0.0, Material::new(0.5, 0.0));
+    ground.transform.position = Vec2::new(-10.0, 0.0);
+    ground.set_static();
+    world.add_body(ground);
+    
+    let mut ball = Body::new(Circle::new(0.5).into_shape(), 1.0, Material::new(0.5, 0.0));
+    ball.transform.position = Vec2::new(-6.0, 3.0);
+    let ball = world.add_body(ball);
+    
+    let mut crossed_bottom = false;
+    
+    for _ in 0..600 {
+        world.update(DT);
+        
+        // The ball rolls back and forth on the surface without falling through or leaving the valley
+        let position = world.get_body(ball).transform.position;
+        assert!(position.y - 0.04 * position.x * position.x > 0.45);
+        assert!(position.x.abs() < 8.0);
+        
+        crossed_bottom |= position.x > 0.0;
+    }
+    
+    assert!(crossed_bottom);
+}
+
+#[test]
+fn distance_between_bodies() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Chain::new(vec![Vec2::new(-5.0, 0.0), Vec2::new(0.0, 0.0), Vec2::new(5.0, 1.0)]).into_shape(),
+                               0.0, Material::new(0.0, 0.0));
+    ground.set_static();
+    let ground = world.add_body(ground);
+    
+    let mut body = dumbbell();
+    body.transform.position = Vec2::new(-2.0, 3.0);
+    let body = world.add_body(body);
+    
+    // The balls are the closest fixtures to the flat part of the chain
+    let output = world.distance(body, ground).unwrap();
+    assert!((output.distance - 2.5).abs() < 1e-4);
+    assert!((output.point_a.y - 2.5).abs() < 1e-4);
+    assert!(output.point_b.y.abs() < 1e-4);
+    
+    world.get_body_mut(body).transform.position = Vec2::new(-2.0, 0.2);
+    assert_eq!(world.distance(ground, body).unwrap().distance, 0.0);
+}
+
+/// An axis aligned box with the given half extents, as a user-defined support mapping.
+struct SupportBox(Vec2);
+
+impl Support for SupportBox {
+    fn support_point(&self, dir: &Vec2) -> Vec2 {
+        Vec2::new(if dir.x >= 0.0 { self.0.x } else { -self.0.x }, if dir.y >= 0.0 { self.0.y } else { -self.0.y })
+    }
+    
+    fn radius(&self) -> f32 {
+        0.0
+    }
+}
+
+#[test]
+fn convex_bodies_rest_on_ground() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(20.0, 1.0).into_shape(), 0.0, Material::new(0.4, 0.0));
+    ground.transform.position = Vec2::new(0.0, -0.5);
+    ground.set_static();
+    world.add_body(ground);
+    
+    let mut bodies = Vec::new();
+    
+    for &x in [-4.0, 0.0, 4.0].iter() {
+        let mut body = Body::new(Convex::new(SupportBox(Vec2::new(0.5, 0.5))).unwrap().into_shape(), 1.0, Material::new(0.4, 0.0));
+        body.transform.position = Vec2::new(x, 0.6);
+        bodies.push(world.add_body(body));
+    }
+    
+    // A convex box on a polygon box, and a circle on a convex box
+    let mut top = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 1.0, Material::new(0.4, 0.0));
+    top.transform.position = Vec2::new(0.2, 1.6);
+    bodies.push(world.add_body(top));
+    
+    let mut ball = Body::new(Circle::new(0.5).into_shape(), 1.0, Material::new(0.4, 0.0));
+    ball.transform.position = Vec2::new(4.0, 1.6);
+    bodies.push(world.add_body(ball));
+    
+    assert!((world.get_body(bodies[0]).mass - 1.0).abs() < 1e-5);
+    
+    for _ in 0..600 {
+        world.update(DT);
+    }
+    
+    let expected_y = [0.5, 0.5, 0.5, 1.5, 1.5];
+    
+    for (&body, &y) in bodies.iter().zip(expected_y.iter()) {
+        let body = world.get_body(body);
+        assert!((body.transform.position.y - y).abs() < 0.02);
+        assert!(body.transform.rotation().abs() < 0.01);
+        assert!(body.velocity.len() < 0.01);
+    }
+}
+
+#[test]
+fn ellipse_rocks_on_ground() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(20.0, 1.0).into_shape(), 0.0, Material::new(0.6, 0.0));
+    ground.transform.position = Vec2::new(0.0, -0.5);
+    ground.set_static();
+    world.add_body(ground);
+    
+    let ellipse = Ellipse::new(Vec2::new(1.0, 0.5));
+    
+    let mut resting = Body::new(ellipse.clone().into_shape(), 1.0, Material::new(0.6, 0.0));
+    resting.transform.position = Vec2::new(-5.0, 0.5);
+    let resting = world.add_body(resting);
+    
+    let mut tilted = Body::new(ellipse.into_shape(), 1.0, Material::new(0.6, 0.0));
+    // Resting on its lowest point, which is 0.65 below its center
+    tilted.transform.position = Vec2::new(5.0, 0.645);
+    tilted.transform.set_rotation(0.5);
+    let tilted = world.add_body(tilted);
+    
+    let mut levelled = false;
+    
+    for _ in 0..600 {
+        world.update(DT);
+        
+        // The tilted ellipse rolls back and forth on its smooth boundary without sinking in
+        let body = world.get_body(tilted);
+        let lowest = body.bounds.center().y - body.bounds.extents().y;
+        assert!(lowest > -0.02);
+        assert!((body.transform.position.x - 5.0).abs() < 1.0);
+        
+        levelled |= body.transform.rotation().abs() < 0.05;
+    }
+    
+    assert!(levelled);
+    
+    let body = world.get_body(resting);
+    assert!((body.transform.position - Vec2::new(-5.0, 0.5)).len() < 0.02);
+    assert!(body.transform.rotation().abs() < 0.01);
+    assert!(body.velocity.len() < 0.01);
+}
+
+/// A bar with a ball of radius 0.5 at each end, at x = -1 and x = 1.
+fn dumbbell() -> Body {
+    let bar = Polygon::new(vec![Vec2::new(-1.0, -0.1), Vec2::new(1.0, -0.1), Vec2::new(1.0, 0.1), Vec2::new(-1.0, 0.1)]);
+    
+    let mut fixtures = vec![Fixture::new(bar.into_shape(), 1.0, Material::new(0.4, 0.0))];
+    
+    for &x in [-1.0, 1.0].iter() {
+        let mut ball = Fixture::new(Circle::new(0.5).into_shape(), 1.0, Material::new(0.4, 0.0));
+        ball.transform = Transform::new(Vec2::new(x, 0.0), 0.0);
+        fixtures.push(ball);
+    }
+    
+    Body::with_fixtures(fixtures)
+}
+
+#[test]
+fn compound_body_mass() {
+    let body = dumbbell();
+    
+    let bar_mass = 2.0 * 0.2;
+    let ball_mass = math::PI * 0.25;
+    
+    let bar_inertia = bar_mass * (2.0 * 2.0 + 0.2 * 0.2) / 12.0;
+    let ball_inertia = 0.5 * ball_mass * 0.25 + ball_mass * 1.0;
+    
+    assert!((body.mass - (bar_mass + 2.0 * ball_mass)).abs() < 1e-5);
+    assert!((body.inertia - (bar_inertia + 2.0 * ball_inertia)).abs() < 1e-5);
+    
+    assert_eq!(body.bounds.center(), Vec2::ZERO);
+    assert_eq!(body.bounds.extents(), Vec2::new(1.5, 0.5));
+}
+
+#[test]
+fn compound_body_rests_on_ground() {
+    let mut body = dumbbell();
+    body.transform.position = Vec2::UP * 2.0;
+    
+    let world = settle_on_ground(body);
+    let body = world.bodies_iter().find(|body| !body.is_static()).unwrap();
+    
+    assert!((body.transform.position.y - 0.5).abs() < 0.02);
+    assert!(body.transform.rotation().abs() < 0.01);
+    
+    // Only the balls touch the ground, each with a single contact
+    let contacts = world.contacts();
+    assert_eq!(contacts.len(), 2);
+    
+    let mut fixtures: Vec<usize> = contacts.iter().map(|contact| contact.fixture_b).collect();
+    fixtures.sort_unstable();
+    assert_eq!(fixtures, vec![1, 2]);
+}
+
+#[test]
+fn filtered_fixtures_do_not_collide() {
+    let mut world = World {
+        gravity: Vec2::ZERO,
+        ..World::default()
+    };
+    
+    let mut fixture = Fixture::new(Circle::new(0.5).into_shape(), 1.0, Material::new(0.4, 0.0));
+    fixture.filter = Filter::new(0x0002, !0x0002, 0);
+    
+    let mut ghosts = Vec::new();
+    
+    for i in 0..2 {
+        let mut body = Body::with_fixtures(vec![fixture.clone()]);
+        body.transform.position = Vec2::RIGHT * 0.5 * i as f32;
+        ghosts.push(world.add_body(body));
+    }
+    
+    let mut other = Body::new(Circle::new(0.5).into_shape(), 1.0, Material::new(0.4, 0.0));
+    other.transform.position = Vec2::LEFT * 0.5;
+    let other = world.add_body(other);
+    
+    world.update(DT);
+    
+    assert!(!world.contact_constraints.contains_key(&BodyPair::new(ghosts[0], ghosts[1])));
+    assert_eq!(contacts(&world, ghosts[0], other).len(), 1);
+}
+
+/// A door of length 2 with its origin at the hinge end.
+fn door() -> Body {
+    let vertices = vec![Vec2::new(0.0, -0.1), Vec2::new(2.0, -0.1), Vec2::new(2.0, 0.1), Vec2::new(0.0, 0.1)];
+    Body::new(Polygon::new(vertices).into_shape(), 1.0, Material::new(0.4, 0.0))
+}
+
+#[test]
+fn off_center_body_mass() {
+    let body = door();
+    
+    match *body.shape() {
+        Shape::Polygon(ref polygon) => assert_eq!(polygon.vertices[0], Vec2::new(0.0, -0.1)),
+        _ => panic!("expected a polygon"),
+    }
+    
+    let mass = 2.0 * 0.2;
+    
+    assert!((body.mass - mass).abs() < 1e-5);
+    assert!((body.local_center() - Vec2::new(1.0, 0.0)).len() < 1e-5);
+    assert!((body.inertia - mass * (2.0 * 2.0 + 0.2 * 0.2) / 12.0).abs() < 1e-5);
+}
+
+#[test]
+fn off_center_body_spins_about_center_of_mass() {
+    let mut world = World {
+        gravity: Vec2::ZERO,
+        ..World::default()
+    };
+    
+    let mut body = door();
+    body.angular_vel = math::PI;
+    let body = world.add_body(body);
+    
+    for _ in 0..60 {
+        world.update(DT);
+    }
+    
+    // Half a turn about the center of mass leaves the hinge at the far end
+    let body = world.get_body(body);
+    
+    assert!((body.world_center() - Vec2::new(1.0, 0.0)).len() < 1e-4);
+    assert!((body.transform.position - Vec2::new(2.0, 0.0)).len() < 1e-4);
+}
+
+#[test]
+fn off_center_body_rests_on_ground() {
+    let vertices = vec![Vec2::ZERO, Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+    
+    let mut body = Body::new(Polygon::new(vertices).into_shape(), 10.0, Material::new(0.4, 0.0));
+    body.transform.position = Vec2::UP * 1.5;
+    
+    let world = settle_on_ground(body);
+    let body = world.bodies_iter().find(|body| !body.is_static()).unwrap();
+    
+    assert!((body.world_center().y - 0.5).abs() < 0.02);
+    assert!(body.transform.position.y.abs() < 0.02);
+    assert!(body.transform.rotation().abs() < 0.01);
+}
+
+#[test]
+fn rounded_polygon_rests_on_ground() {
+    let core = Polygon::rect(0.8, 0.8).vertices;
+    
+    for shape in [Polygon::new_rounded(core.clone(), 0.1).into_shape(), Circle::new(0.5).into_shape()].iter() {
+        let mut rounded_box = Body::new(Polygon::new_rounded(core.clone(), 0.1).into_shape(), 10.0, Material::new(0.4, 0.0));
+        rounded_box.transform.position = Vec2::UP * 2.0;
+        
+        let mut top = Body::new(shape.clone(), 10.0, Material::new(0.4, 0.0));
+        top.transform.position = Vec2::UP * 3.5;
+        
+        let mut world = settle_on_ground(rounded_box);
+        let top = world.add_body(top);
+        
+        for _ in 0..240 {
+            world.update(DT);
+        }
+        
+        let top = world.get_body(top);
+        let rounded_box = world.bodies_iter().find(|body| !body.is_static() && body.id != top.id).unwrap();
+        
+        assert!((rounded_box.transform.position.y - 0.5).abs() < 0.02);
+        assert!(rounded_box.transform.rotation().abs() < 0.01);
+        assert!((top.transform.position.y - 1.5).abs() < 0.03);
+        assert!(top.transform.position.x.abs() < 0.01);
+    }
+}
+
+#[test]
+fn box_stack_stays_upright() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(40.0, 1.0).into_shape(), 10.0, Material::new(0.6, 0.0));
+    ground.transform.position = Vec2::DOWN * 0.5;
+    ground.set_static();
+    let ground = world.add_body(ground);
+    
+    // Boxes start barely overlapping, so each pair is in contact from the first step
+    let boxes: Vec<BodyId> = (0..10).map(|i| {
+        let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, Material::new(0.6, 0.0));
+        body.transform.position = Vec2::UP * (0.495 + 0.995 * i as f32);
+        world.add_body(body)
+    }).collect();
+    
+    for _ in 0..600 {
+        world.update(DT);
+    }
+    
+    // Each pair of faces rests on a two point manifold solved by the block solver
+    assert!(world.contact_constraints[&BodyPair::new(ground, boxes[0])][0].block.is_some());
+    
+    // Solving the points one at a time leaves the stack swaying, faster towards the top
+    for id in boxes.iter() {
+        let body = world.get_body(*id);
+        
+        assert!(body.transform.position.x.abs() < 1e-4);
+        assert!(body.transform.rotation().abs() < 1e-4);
+        assert!(body.velocity.len() < 1e-3);
+        assert!(body.angular_vel.abs() < 1e-3);
+    }
+}
+
+#[test]
+fn rolling_friction_stops_balls() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(100.0, 1.0).into_shape(), 10.0, Material::new(0.6, 0.0));
+    ground.transform.position = Vec2::DOWN * 0.5;
+    ground.set_static();
+    world.add_body(ground);
+    
+    let balls: Vec<BodyId> = [0.0, 0.1].iter().enumerate().map(|(i, &rolling_friction)| {
+        let material = Material::new(0.6, 0.0).with_rolling_friction(rolling_friction);
+        
+        let mut ball = Body::new(Circle::new(0.5).into_shape(), 10.0, material);
+        ball.transform.position = Vec2::new(-20.0 * i as f32, 0.495);
+        ball.velocity = Vec2::RIGHT * 2.0;
+        ball.angular_vel = -4.0;
+        world.add_body(ball)
+    }).collect();
+    
+    for _ in 0..300 {
+        world.update(DT);
+    }
+    
+    // Without rolling friction the ball rolls on, with it the ball decelerates at about 0.1 * g / 1.5
+    let (free, resisted) = (world.get_body(balls[0]), world.get_body(balls[1]));
+    
+    assert!((free.velocity.x - 2.0).abs() < 0.05);
+    assert!(resisted.velocity.len() < 0.01);
+    assert!(resisted.angular_vel.abs() < 0.02);
+    assert!((resisted.transform.position.x + 20.0 - 2.0 * 2.0 / (2.0 * 0.98 / 1.5)).abs() < 0.2);
+}
+
+#[test]
+fn material_pair_overrides_friction() {
+    const ICE: MaterialId = 1;
+    const RUBBER: MaterialId = 2;
+    
+    let mut world = World::default();
+    world.set_material_pair(ICE, RUBBER, MaterialPair::new(0.0, 0.0));
+    
+    let mut ground = Body::new(Polygon::rect(100.0, 1.0).into_shape(), 10.0, Material::new(0.5, 0.0).with_id(ICE));
+    ground.transform.position = Vec2::DOWN * 0.5;
+    ground.set_static();
+    world.add_body(ground);
+    
+    // Rubber slides on ice without friction, while a box of another material with the same friction
+    // comes to rest
+    let boxes: Vec<BodyId> = [RUBBER, 3].iter().enumerate().map(|(i, &id)| {
+        let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, Material::new(0.5, 0.0).with_id(id));
+        body.transform.position = Vec2::new(-20.0 * i as f32, 0.495);
+        body.velocity = Vec2::RIGHT * 2.0;
+        world.add_body(body)
+    }).collect();
+    
+    for _ in 0..120 {
+        world.update(DT);
+    }
+    
+    assert!((world.get_body(boxes[0]).velocity.x - 2.0).abs() < 0.01);
+    assert!(world.get_body(boxes[1]).velocity.len() < 0.01);
+}
+
+#[test]
+fn conveyor_carries_box() {
+    let mut world = World::default();
+    
+    let belt = Material::new(0.8, 0.0).with_surface_velocity(1.5);
+    let mut conveyor = Body::new(Polygon::rect(100.0, 1.0).into_shape(), 10.0, belt);
+    conveyor.transform.position = Vec2::DOWN * 0.5;
+    conveyor.set_static();
+    world.add_body(conveyor);
+    
+    // The surface moves clockwise, carrying a box on top to the right
+    let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, Material::new(0.8, 0.0));
+    body.transform.position = Vec2::UP * 0.495;
+    let body = world.add_body(body);
+    
+    for _ in 0..120 {
+        world.update(DT);
+    }
+    
+    let body = world.get_body(body);
+    assert!((body.velocity.x - 1.5).abs() < 0.01);
+    assert!(body.transform.position.x > 2.0);
+}
+
+#[test]
+fn anisotropic_friction_slides_along_blade() {
+    let mut world = World::default();
+    
+    let mut ice = Body::new(Polygon::rect(100.0, 1.0).into_shape(), 10.0, Material::new(1.0, 0.0));
+    ice.transform.position = Vec2::DOWN * 0.5;
+    ice.set_static();
+    world.add_body(ice);
+    
+    // A skate slides freely along its blade, the local x axis, but not across it
+    let skates: Vec<BodyId> = [0.0, math::PI / 2.0].iter().enumerate().map(|(i, &rotation)| {
+        let blade = Material::new(1.0, 0.0).with_anisotropic_friction(Vec2::new(0.0, 1.0));
+        
+        let mut skate = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 10.0, blade);
+        skate.transform = Transform::new(Vec2::new(-20.0 * i as f32, 0.495), rotation);
+        skate.velocity = Vec2::RIGHT * 2.0;
+        world.add_body(skate)
+    }).collect();
+    
+    for _ in 0..120 {
+        world.update(DT);
+    }
+    
+    assert!((world.get_body(skates[0]).velocity.x - 2.0).abs() < 0.01);
+    assert!(world.get_body(skates[1]).velocity.len() < 0.01);
+}
+
+#[test]
+fn damping_and_speed_limits() {
+    let mut world = World {
+        gravity: Vec2::ZERO,
+        ..World::default()
+    };
+    
+    let mut damped = Body::new(Circle::new(0.5).into_shape(), 1.0, Material::default());
+    damped.velocity = Vec2::RIGHT * 2.0;
+    damped.angular_vel = 2.0;
+    damped.linear_damping = 1.0;
+    damped.angular_damping = 1.0;
+    let damped = world.add_body(damped);
+    
+    let mut limited = Body::new(Circle::new(0.5).into_shape(), 1.0, Material::default());
+    limited.transform.position = Vec2::UP * 5.0;
+    limited.max_linear_speed = Some(1.0);
+    limited.max_angular_speed = Some(0.5);
+    let limited = world.add_body(limited);
+    
+    for _ in 0..60 {
+        world.get_body_mut(limited).add_force(Vec2::RIGHT * 10.0);
+        world.get_body_mut(limited).add_torque(10.0);
+        world.update(DT);
+    }
+    
+    // One second of damping at a rate of 1 leaves about 1 / e of the velocity
+    let expected = 2.0 * (1.0 / (1.0 + DT)).powi(60);
+    let damped = world.get_body(damped);
+    
+    assert!((damped.velocity.x - expected).abs() < 1e-4);
+    assert!((damped.angular_vel - expected).abs() < 1e-4);
+    
+    let limited = world.get_body(limited);
+    
+    assert!((limited.velocity.len() - 1.0).abs() < 1e-4);
+    assert!((limited.angular_vel - 0.5).abs() < 1e-4);
+}
+
+#[test]
+fn fixed_rotation_keeps_body_upright() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(100.0, 1.0).into_shape(), 10.0, Material::new(0.8, 0.0));
+    ground.transform.position = Vec2::DOWN * 0.5;
+    ground.set_static();
+    world.add_body(ground);
+    
+    let mut player = Body::new(Polygon::rect(1.0, 2.0).into_shape(), 1.0, Material::new(0.8, 0.0));
+    player.transform.position = Vec2::UP * 0.995;
+    player.set_fixed_rotation(true);
+    let inertia = player.inertia;
+    let player = world.add_body(player);
+    
+    // Pushing the top of a tall box along high friction ground would tip it over
+    for _ in 0..120 {
+        let top = world.get_body(player).world_center() + Vec2::UP;
+        world.get_body_mut(player).add_force_at_pos(Vec2::RIGHT * 20.0, top);
+        world.update(DT);
+    }
+    
+    let player = world.get_body(player);
+    
+    assert_eq!(player.transform.rotation(), 0.0);
+    assert_eq!(player.inertia, inertia);
+    assert_eq!(player.inv_inertia, 0.0);
+    assert!(player.transform.position.x > 1.0);
+}
+
+#[test]
+fn mass_overrides_and_density_changes() {
+    let mut body = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 1.0, Material::default());
+    
+    body.set_density(3.0);
+    assert!((body.mass() - 3.0).abs() < 1e-5);
+    assert!((body.inertia() - 3.0 / 6.0).abs() < 1e-5);
+    assert!((body.inv_mass() - 1.0 / 3.0).abs() < 1e-5);
+    
+    body.set_mass_properties(10.0, 5.0, Vec2::new(0.25, 0.0));
+    assert_eq!(body.mass(), 10.0);
+    assert_eq!(body.inv_inertia(), 0.2);
+    assert_eq!(body.local_center(), Vec2::new(0.25, 0.0));
+    
+    body.reset_mass_from_shape();
+    assert!((body.mass() - 3.0).abs() < 1e-5);
+    assert_eq!(body.local_center(), Vec2::ZERO);
+    
+    // Static bodies stay massless
+    body.set_static();
+    body.set_density(2.0);
+    body.set_mass_properties(10.0, 5.0, Vec2::ZERO);
+    assert!(body.is_static());
+    assert_eq!(body.mass(), 0.0);
+}
+
+#[test]
+fn moving_center_of_mass_keeps_velocity_of_origin() {
+    let mut body = Body::new(Circle::new(1.0).into_shape(), 1.0, Material::default());
+    body.angular_vel = 2.0;
+    
+    body.set_mass_properties(1.0, 1.0, Vec2::RIGHT);
+    
+    // The origin does not move, so the center of mass now moves around it
+    assert_eq!(body.transform.position, Vec2::ZERO);
+    assert!((body.velocity - Vec2::UP * 2.0).len() < 1e-5);
+}
+
+#[test]
+fn growing_shape_pushes_neighbour() {
+    let mut world = World {
+        gravity: Vec2::ZERO,
+        ..World::default()
+    };
+    
+    let ball = world.add_body(Body::new(Circle::new(0.5).into_shape(), 1.0, Material::default()));
+    
+    let mut neighbour = Body::new(Circle::new(0.5).into_shape(), 1.0, Material::default());
+    neighbour.transform.position = Vec2::RIGHT * 1.2;
+    let neighbour = world.add_body(neighbour);
+    
+    world.update(DT);
+    assert!(world.distance(ball, neighbour).unwrap().distance > 0.0);
+    
+    world.set_shape(ball, Circle::new(1.0).into_shape());
+    assert!((world.get_body(ball).mass() - math::PI).abs() < 1e-4);
+    assert!(world.get_body(ball).bounds.intersects(&world.get_body(neighbour).bounds));
+    
+    for _ in 0..60 {
+        world.update(DT);
+    }
+    
+    assert!(world.get_body(neighbour).transform.position.x > 1.4);
+    assert!(world.get_body(ball).transform.position.x < 0.0);
+}
+
+#[test]
+fn shrinking_static_shape_keeps_resting_body() {
+    let mut world = World::default();
+    
+    let mut ground = Body::new(Polygon::rect(10.0, 1.0).into_shape(), 1.0, Material::default());
+    ground.set_static();
+    let ground = world.add_body(ground);
+    
+    let mut block = Body::new(Polygon::rect(1.0, 1.0).into_shape(), 1.0, Material::default());
+    block.transform.position = Vec2::UP * 0.995;
+    let block = world.add_body(block);
+    
+    for _ in 0..10 {
+        world.update(DT);
+    }
+    
+    // The old contacts are dropped, and the new shape fits in the bounds of the old one
+    world.set_shape(ground, Polygon::rect(9.5, 1.0).into_shape());
+    assert!(!world.contact_constraints.contains_key(&BodyPair::new(ground, block)));
+    
+    // The pair is found again at once, before the block sinks into the ground
+    world.update(DT);
+    assert!(!contacts(&world, ground, block).is_empty());
+    
+    for _ in 0..60 {
+        world.update(DT);
+    }
+    
+    assert!(world.get_body(block).transform.position.y > 0.99);
+}
+
+#[test]
+fn set_filter_starts_and_ends_contacts() {
+    let mut world = World {
+        gravity: Vec2::ZERO,
+        ..World::default()
+    };
+    
+    let mut ground = Body::new(Polygon::rect(10.0, 1.0).into_shape(), 1.0, Material::default());
+    ground.set_static();
+    let ground = world.add_body(ground);
+    
+    let mut fixture = Fixture::new(Polygon::rect(1.0, 1.0).into_shape(), 1.0, Material::default());
+    fixture.filter = Filter::new(0x0002, 0x0000, 0);
+    fixture.transform.position = Vec2::UP * 0.995;
+    let block = world.add_body(Body::with_fixtures(vec![fixture]));
+    
+    let touching = |world: &World| world.contact_constraints.get(&BodyPair::new(ground, block))
+        .into_iter().flatten().next().is_some();
+    
+    world.update(DT);
+    assert!(!touching(&world));
+    
+    // The block does not move, so only the new filter can start the contact
+    world.set_filter(block, 0, Filter::default());
+    world.update(DT);
+    assert!(touching(&world));
+    
+    world.set_filter(block, 0, Filter::new(0x0002, 0x0000, 0));
+    world.update(DT);
+    assert!(!touching(&world));
+}
@@ -1,5 +1,8 @@
 use crate::math::{Vec2, Mat2};
 
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SerializedTransform", into = "SerializedTransform"))]
 pub struct Transform {
     pub position: Vec2,
     
@@ -96,4 +99,46 @@ impl Transform {
     pub fn local_dir(&self, world_dir: &Vec2) -> Vec2 {
         self.rot_matrix.transpose() * world_dir
     }
+
+    /// Returns the world space transform for the given transform relative to this `Transform`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use physics2d::Transform;
+    /// # use physics2d::{Vec2, math};
+    ///
+    /// let t = Transform::new(Vec2::new(1.0, 2.0), math::PI / 2.0);
+    /// let world = t.world_transform(&Transform::new(Vec2::new(1.0, 0.0), math::PI / 2.0));
+    ///
+    /// assert!((world.position - Vec2::new(1.0, 3.0)).len() < 1e-07);
+    /// assert_eq!(world.rotation(), math::PI);
+    /// ```
+    pub fn world_transform(&self, local: &Transform) -> Transform {
+        Transform::new(self.world_pos(&local.position), self.rotation + local.rotation)
+    }
+}
+
+/// The serialized form of a `Transform`. The rotation matrix is rebuilt from the angle on load.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedTransform {
+    position: Vec2,
+    rotation: f32,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedTransform> for Transform {
+    fn from(transform: SerializedTransform) -> Transform {
+        Transform::new(transform.position, transform.rotation)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Transform> for SerializedTransform {
+    fn from(transform: Transform) -> SerializedTransform {
+        SerializedTransform {
+            position: transform.position,
+            rotation: transform.rotation,
+        }
+    }
 }